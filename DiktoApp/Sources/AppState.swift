@@ -89,9 +89,9 @@ final class AppCallback: TranscriptionCallback, @unchecked Sendable {
         }
     }
 
-    func onFinalSegment(text: String) {
+    func onFinalSegment(segment: TranscriptSegment) {
         DispatchQueue.main.async { [weak self] in
-            self?.appState?.partialText = text
+            self?.appState?.partialText = segment.text
             self?.appState?.updateOverlay()
         }
     }
@@ -104,6 +104,17 @@ final class AppCallback: TranscriptionCallback, @unchecked Sendable {
         }
     }
 
+    func onWarning(message: String) {
+        NSLog("[Dikto] Warning: \(message)")
+    }
+
+    func onLevel(level: InputLevel) {
+        DispatchQueue.main.async { [weak self] in
+            guard let appState = self?.appState, appState.isRecording, !appState.isProcessing else { return }
+            appState.overlayController.updateLevel(level)
+        }
+    }
+
     func onStateChange(state: RecordingState) {
         DispatchQueue.main.async { [weak self] in
             guard let appState = self?.appState else { return }
@@ -115,7 +126,8 @@ final class AppCallback: TranscriptionCallback, @unchecked Sendable {
             case .processing:
                 appState.isProcessing = true
                 appState.overlayController.show(text: appState.partialText, isProcessing: true)
-            case let .done(text):
+            case let .done(text, audioPath):
+                if let audioPath { NSLog("[Dikto] Session audio saved to \(audioPath)") }
                 appState.isRecording = false
                 appState.isProcessing = false
                 appState.modelInMemory = true
@@ -151,11 +163,16 @@ final class DownloadCallback: DownloadProgressCallback, @unchecked Sendable {
         }
     }
 
+    func onFileProgress(filename: String, bytesDownloaded: UInt64, totalBytes: UInt64) {
+        // The UI shows one bar per model; onProgress already sums all files
+    }
+
     func onComplete(modelName: String) {
         DispatchQueue.main.async { [self] in
             guard let appState = self.appState else { return }
             appState.downloadProgress.removeValue(forKey: modelName)
             appState.activeDownloadCallback = nil
+            appState.activeDownloadHandle = nil
             // Auto-switch to the downloaded model if none is currently available
             if !appState.modelAvailable {
                 appState.switchModel(name: modelName)
@@ -172,9 +189,19 @@ final class DownloadCallback: DownloadProgressCallback, @unchecked Sendable {
             guard let appState = self.appState else { return }
             appState.downloadProgress.removeValue(forKey: name)
             appState.activeDownloadCallback = nil
+            appState.activeDownloadHandle = nil
             appState.lastError = "Download failed: \(error)"
         }
     }
+
+    func onCancelled(modelName: String) {
+        DispatchQueue.main.async { [self] in
+            guard let appState = self.appState else { return }
+            appState.downloadProgress.removeValue(forKey: modelName)
+            appState.activeDownloadCallback = nil
+            appState.activeDownloadHandle = nil
+        }
+    }
 }
 
 @MainActor
@@ -198,6 +225,7 @@ final class AppState: ObservableObject {
     private var sessionHandle: SessionHandle?
     private var activeCallback: AppCallback?
     var activeDownloadCallback: DownloadCallback?
+    var activeDownloadHandle: DownloadHandle?
     private var hotKeyRef: EventHotKeyRef?
     private var pressedHandlerRef: EventHandlerRef?
     private var releasedHandlerRef: EventHandlerRef?
@@ -399,9 +427,27 @@ final class AppState: ObservableObject {
         refreshModels()
         refreshConfig()
         refreshLanguages()
+        refreshCatalog()
         NSLog("[Dikto] Engine ready. Model available on disk: \(modelAvailable)")
     }
 
+    /// Fetch the model catalog (if `catalog_url` is configured) in the
+    /// background, then reload the model list to show new models and updates.
+    func refreshCatalog() {
+        guard let engine, config?.catalogUrl != nil else { return }
+        DispatchQueue.global(qos: .utility).async {
+            do {
+                let count = try engine.refreshCatalog()
+                NSLog("[Dikto] Model catalog refreshed: \(count) model(s)")
+                DispatchQueue.main.async { [weak self] in
+                    self?.refreshModels()
+                }
+            } catch {
+                NSLog("[Dikto] Model catalog refresh failed: \(error)")
+            }
+        }
+    }
+
     func refreshModels() {
         guard let engine else { return }
         models = engine.listModels()
@@ -465,7 +511,11 @@ final class AppState: ObservableObject {
             language: cfg.language,
             maxDuration: cfg.maxDuration,
             silenceDurationMs: cfg.silenceDurationMs,
-            speechThreshold: cfg.speechThreshold
+            speechThreshold: cfg.speechThreshold,
+            continuous: false,
+            saveAudio: cfg.saveAudio,
+            saveAudioMaxFiles: cfg.saveAudioMaxFiles,
+            saveAudioMaxAgeDays: cfg.saveAudioMaxAgeDays
         )
 
         partialText = ""
@@ -565,13 +615,25 @@ final class AppState: ObservableObject {
         }
     }
 
+    /// Delete a downloaded model's files. The core refuses the active model.
+    func deleteModel(name: String) {
+        guard let engine else { return }
+        do {
+            try engine.deleteModel(modelName: name)
+            lastError = nil
+            refreshModels()
+        } catch {
+            lastError = "Delete failed: \(error.localizedDescription)"
+        }
+    }
+
     func downloadModel(name: String) {
         guard let engine else { return }
         let callback = DownloadCallback(appState: self, modelName: name)
         activeDownloadCallback = callback  // retain until completion
         downloadProgress[name] = 0.0
         do {
-            try engine.downloadModel(modelName: name, callback: callback)
+            activeDownloadHandle = try engine.downloadModel(modelName: name, callback: callback)
         } catch {
             activeDownloadCallback = nil
             downloadProgress.removeValue(forKey: name)
@@ -579,6 +641,12 @@ final class AppState: ObservableObject {
         }
     }
 
+    /// Cancel the running download. Progress is cleared once the core reports
+    /// the cancellation; the partial file is kept or removed per config.
+    func cancelDownload() {
+        activeDownloadHandle?.cancel()
+    }
+
     func updateConfig(_ newConfig: DiktoConfig) {
         guard let engine else { return }
 
@@ -312,12 +312,24 @@ struct OnboardingView: View {
 
             Group {
                 if let progress = appState.downloadProgress[model.name] {
-                    VStack(spacing: 2) {
-                        ProgressView(value: progress)
-                            .frame(width: 60)
-                        Text("\(Int(progress * 100))%")
-                            .font(.system(size: 9))
-                            .foregroundStyle(.secondary)
+                    HStack(spacing: Theme.Spacing.xxs) {
+                        VStack(spacing: 2) {
+                            ProgressView(value: progress)
+                                .frame(width: 60)
+                            Text("\(Int(progress * 100))%")
+                                .font(.system(size: 9))
+                                .foregroundStyle(.secondary)
+                        }
+                        Button {
+                            // Not a failure: forget the download before progress is cleared
+                            downloadingModelName = nil
+                            appState.cancelDownload()
+                        } label: {
+                            Image(systemName: "xmark.circle.fill")
+                        }
+                        .buttonStyle(.borderless)
+                        .foregroundStyle(.secondary)
+                        .help("Cancel download")
                     }
                     .transition(.opacity)
                 } else if model.isDownloaded {
@@ -6,9 +6,14 @@ final class RecordingOverlayController {
     private var panel: NSPanel?
     private var hostingView: NSHostingView<RecordingOverlayView>?
     private var isHiding = false
+    private var text = ""
+    private var isProcessing = false
+    private var level: InputLevel?
 
     func show(text: String, isProcessing: Bool) {
-        let view = RecordingOverlayView(text: text, isProcessing: isProcessing)
+        self.text = text
+        self.isProcessing = isProcessing
+        let view = RecordingOverlayView(text: text, isProcessing: isProcessing, level: level)
 
         // Cancel any in-progress hide animation
         isHiding = false
@@ -57,8 +62,16 @@ final class RecordingOverlayController {
         }
     }
 
+    /// Redraw the level meter; the panel must already be shown.
+    func updateLevel(_ level: InputLevel) {
+        self.level = level
+        guard let hostingView, !isHiding else { return }
+        hostingView.rootView = RecordingOverlayView(text: text, isProcessing: isProcessing, level: level)
+    }
+
     func hide() {
         isHiding = true
+        level = nil
 
         NSAnimationContext.runAnimationGroup { context in
             context.duration = 0.15
@@ -77,6 +90,7 @@ final class RecordingOverlayController {
 struct RecordingOverlayView: View {
     let text: String
     let isProcessing: Bool
+    var level: InputLevel?
     @State private var isPulsing = false
 
     var body: some View {
@@ -104,6 +118,10 @@ struct RecordingOverlayView: View {
             .accessibilityAddTraits(.updatesFrequently)
 
             Spacer()
+
+            if !isProcessing, let level {
+                LevelMeterView(level: level)
+            }
         }
         .accessibilityElement(children: .combine)
         .padding(.horizontal, Theme.Spacing.lg)
@@ -119,3 +137,26 @@ struct RecordingOverlayView: View {
         )
     }
 }
+
+/// Input level bar: RMS from -60 to 0 dBFS, tinted while speech is detected.
+struct LevelMeterView: View {
+    let level: InputLevel
+
+    private var fraction: CGFloat {
+        CGFloat(min(max((level.rmsDbfs + 60) / 60, 0), 1))
+    }
+
+    var body: some View {
+        GeometryReader { geometry in
+            ZStack(alignment: .leading) {
+                Capsule()
+                    .fill(Color.secondary.opacity(0.2))
+                Capsule()
+                    .fill(level.speechProbability > 0.5 ? Theme.Colors.statusRecording : Color.secondary)
+                    .frame(width: geometry.size.width * fraction)
+            }
+        }
+        .frame(width: 48, height: 4)
+        .accessibilityHidden(true)
+    }
+}
@@ -115,7 +115,14 @@ struct GeneralSettingsView: View {
     @State private var maxDuration: Double = 30
     @State private var silenceDuration: Double = 1500
     @State private var selectedLanguage = "en"
+    @State private var inputDevices: [InputDeviceInfo] = []
+    /// Empty string means the system default input device.
+    @State private var selectedInputDevice = ""
+    /// 0 = all channels mixed, `UInt16.max` = a custom mix from config.json
+    @State private var selectedInputChannel: UInt16 = 0
+    @State private var customChannelMix: [UInt16] = []
     @State private var launchAtLogin = false
+    @State private var saveAudio = false
     @State private var loaded = false
 
     // Shortcut recorder state
@@ -126,6 +133,13 @@ struct GeneralSettingsView: View {
     @State private var shortcutError: String?
     @State private var keyMonitor: Any?
 
+    /// Channel count of the selected (or default) input device.
+    private var selectedDeviceChannels: UInt16 {
+        let device = inputDevices.first(where: { $0.name == selectedInputDevice })
+            ?? inputDevices.first(where: { $0.isDefault })
+        return device?.channels ?? 1
+    }
+
     var body: some View {
         Form {
             Section {
@@ -214,6 +228,43 @@ struct GeneralSettingsView: View {
                     Text("Requires Accessibility permission in System Settings")
                         .font(Theme.Typography.caption)
                         .foregroundStyle(.tertiary)
+                    Toggle("Save recordings", isOn: $saveAudio)
+                        .onChange(of: saveAudio) { guard loaded else { return }; saveSettings() }
+                        .help("Keep the audio of each session to check wrong transcriptions")
+                    if saveAudio {
+                        Text("Saved to ~/.local/share/dikto/recordings")
+                            .font(Theme.Typography.caption)
+                            .foregroundStyle(.tertiary)
+                    }
+                }
+
+                Section("Microphone") {
+                    Picker("Input device", selection: $selectedInputDevice) {
+                        Text("System Default").tag("")
+                        ForEach(inputDevices, id: \.name) { device in
+                            Text(device.name).tag(device.name)
+                        }
+                        if !selectedInputDevice.isEmpty
+                            && !inputDevices.contains(where: { $0.name == selectedInputDevice }) {
+                            Text("\(selectedInputDevice) (not connected)").tag(selectedInputDevice)
+                        }
+                    }
+                    .onChange(of: selectedInputDevice) { guard loaded else { return }; saveSettings() }
+                    .help("Falls back to the system default when the device is not connected")
+                    if selectedDeviceChannels > 1 {
+                        Picker("Input channel", selection: $selectedInputChannel) {
+                            Text("All channels (mixed)").tag(UInt16(0))
+                            ForEach(1...Int(selectedDeviceChannels), id: \.self) { channel in
+                                Text("Channel \(channel)").tag(UInt16(channel))
+                            }
+                            if !customChannelMix.isEmpty {
+                                Text("Channels " + customChannelMix.map(String.init).joined(separator: "+"))
+                                    .tag(UInt16.max)
+                            }
+                        }
+                        .onChange(of: selectedInputChannel) { guard loaded else { return }; saveSettings() }
+                        .help("For audio interfaces: record only the channel the microphone is plugged into")
+                    }
                 }
 
                 if appState.availableLanguages.count > 1 {
@@ -234,7 +285,7 @@ struct GeneralSettingsView: View {
                     Section("Recording") {
                         LabeledContent("Max duration") {
                             HStack(spacing: Theme.Spacing.sm) {
-                                Slider(value: $maxDuration, in: 5...120, step: 5)
+                                Slider(value: $maxDuration, in: 5...600, step: 5)
                                     .onChange(of: maxDuration) { guard loaded else { return }; saveSettings() }
                                     .frame(maxWidth: 160)
                                 Text("\(Int(maxDuration))s")
@@ -329,11 +380,20 @@ struct GeneralSettingsView: View {
         maxDuration = Double(cfg.maxDuration)
         silenceDuration = Double(cfg.silenceDurationMs)
         selectedLanguage = cfg.language
+        inputDevices = listInputDevices()
+        selectedInputDevice = cfg.inputDevice ?? ""
+        if cfg.inputChannels.count > 1 {
+            customChannelMix = cfg.inputChannels
+            selectedInputChannel = .max
+        } else {
+            selectedInputChannel = cfg.inputChannels.first ?? 0
+        }
         if let shortcut = cfg.globalShortcut {
             shortcutValue = shortcut
             shortcutDisplay = formatShortcutForDisplay(shortcut)
         }
         activationMode = cfg.activationMode
+        saveAudio = cfg.saveAudio
         loadLaunchAtLogin()
         loaded = true
     }
@@ -357,17 +417,21 @@ struct GeneralSettingsView: View {
 
     private func saveSettings() {
         guard let cfg = appState.config else { return }
-        let newConfig = DiktoConfig(
-            modelName: cfg.modelName,
-            language: selectedLanguage,
-            maxDuration: UInt32(maxDuration),
-            silenceDurationMs: UInt32(silenceDuration),
-            speechThreshold: cfg.speechThreshold,
-            globalShortcut: shortcutValue,
-            autoPaste: autoPaste,
-            autoCopy: autoCopy,
-            activationMode: activationMode
-        )
+        var newConfig = cfg
+        newConfig.language = selectedLanguage
+        newConfig.maxDuration = UInt32(maxDuration)
+        newConfig.silenceDurationMs = UInt32(silenceDuration)
+        newConfig.globalShortcut = shortcutValue
+        newConfig.autoPaste = autoPaste
+        newConfig.autoCopy = autoCopy
+        newConfig.activationMode = activationMode
+        newConfig.saveAudio = saveAudio
+        newConfig.inputDevice = selectedInputDevice.isEmpty ? nil : selectedInputDevice
+        switch selectedInputChannel {
+        case 0: newConfig.inputChannels = []
+        case .max: newConfig.inputChannels = customChannelMix
+        default: newConfig.inputChannels = [selectedInputChannel]
+        }
         appState.updateConfig(newConfig)
     }
 }
@@ -433,13 +497,37 @@ struct ModelsSettingsView: View {
                                     .foregroundStyle(.secondary)
                             }
                             .transition(.opacity)
+                            Button {
+                                appState.cancelDownload()
+                            } label: {
+                                Image(systemName: "xmark.circle.fill")
+                            }
+                            .buttonStyle(.borderless)
+                            .foregroundStyle(.secondary)
+                            .help("Cancel download")
                         } else if model.isDownloaded {
+                            if model.updateAvailable {
+                                Button("Update") {
+                                    appState.downloadModel(name: model.name)
+                                }
+                                .controlSize(.small)
+                                .disabled(!appState.downloadProgress.isEmpty)
+                                .help("A newer version of this model is available")
+                            }
                             if !isActive(model) {
                                 Button("Use") {
                                     appState.switchModel(name: model.name)
                                 }
                                 .controlSize(.small)
                                 .help("Switch to this model")
+                                Button {
+                                    appState.deleteModel(name: model.name)
+                                } label: {
+                                    Image(systemName: "trash")
+                                }
+                                .buttonStyle(.borderless)
+                                .foregroundStyle(.secondary)
+                                .help("Delete this model from your device")
                             } else {
                                 Text("Active")
                                     .font(Theme.Typography.caption)
@@ -0,0 +1,5 @@
+// The `dikto serve` HTTP and WebSocket server, built as a library so its
+// request handling can be tested from `tests/`.
+
+pub mod serve;
+pub mod stream;
@@ -0,0 +1,211 @@
+use dikto_core::audio::AudioCaptureConfig;
+use dikto_core::level::InputLevel;
+use dikto_core::source::AudioInput;
+use dikto_core::transcribe::TranscriptSegment;
+use dikto_core::{DiktoEngine, ListenConfig, RecordingState, TranscriptionCallback};
+use std::io::{IsTerminal, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::mpsc;
+
+/// Width of the level meter in the status line.
+const METER_WIDTH: usize = 12;
+
+/// Terminal callback: status and partials go to stderr, the final text is
+/// forwarded to the main task so it can be printed to stdout. In continuous
+/// mode each utterance is printed to stdout as soon as it is transcribed.
+struct CliCallback {
+    result_tx: mpsc::UnboundedSender<Result<String, String>>,
+    continuous: bool,
+    /// Draw a level meter in front of the status (only on a terminal).
+    show_meter: bool,
+    /// The status line: meter and text, redrawn together.
+    line: Mutex<(String, String)>,
+}
+
+impl CliCallback {
+    fn status(&self, text: &str) {
+        let mut line = self.line.lock().unwrap_or_else(PoisonError::into_inner);
+        line.1 = text.to_string();
+        draw_line(&line.0, &line.1);
+    }
+
+    /// Set the status text and remove the meter (no longer listening).
+    fn final_status(&self, text: &str) {
+        self.line
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .clear();
+        self.status(text);
+    }
+}
+
+fn draw_line(meter: &str, text: &str) {
+    let mut stderr = std::io::stderr();
+    let _ = write!(stderr, "\r\x1b[2K{meter}{text}");
+    let _ = stderr.flush();
+}
+
+/// Level meter for the status line, e.g. `[=======     ]* `: the RMS level
+/// from -60 to 0 dBFS, with `*` while the VAD hears speech.
+fn meter_bar(level: &InputLevel) -> String {
+    let fraction = ((level.rms_dbfs + 60.0) / 60.0).clamp(0.0, 1.0);
+    let filled = (fraction * METER_WIDTH as f32).round() as usize;
+    let speech = if level.speech_probability >= 0.5 {
+        '*'
+    } else {
+        ' '
+    };
+    format!(
+        "[{}{}]{speech} ",
+        "=".repeat(filled),
+        " ".repeat(METER_WIDTH - filled)
+    )
+}
+
+impl TranscriptionCallback for CliCallback {
+    fn on_partial(&self, text: String) {
+        self.status(&text);
+    }
+
+    fn on_final_segment(&self, segment: TranscriptSegment) {
+        if self.continuous {
+            self.status("");
+            println!("{}", segment.text);
+        }
+    }
+
+    fn on_silence(&self) {
+        if self.continuous {
+            self.status("Listening... (Ctrl-C to stop)");
+        } else {
+            self.status("Silence detected");
+        }
+    }
+
+    fn on_error(&self, error: String) {
+        self.status(&format!("Error: {error}"));
+    }
+
+    fn on_warning(&self, message: String) {
+        // On its own line above the status, so it isn't overwritten
+        let line = self.line.lock().unwrap_or_else(PoisonError::into_inner);
+        eprint!("\r\x1b[2KWarning: {message}\n");
+        draw_line(&line.0, &line.1);
+    }
+
+    fn on_level(&self, level: InputLevel) {
+        if !self.show_meter {
+            return;
+        }
+        let mut line = self.line.lock().unwrap_or_else(PoisonError::into_inner);
+        line.0 = meter_bar(&level);
+        draw_line(&line.0, &line.1);
+    }
+
+    fn on_state_change(&self, state: RecordingState) {
+        match state {
+            RecordingState::Listening => self.status("Listening... (Ctrl-C to stop)"),
+            RecordingState::Processing => self.final_status("Transcribing..."),
+            RecordingState::Done { text, audio_path } => {
+                self.final_status("");
+                if let Some(path) = audio_path {
+                    eprintln!("Audio saved to {path}");
+                }
+                let _ = self.result_tx.send(Ok(text));
+            }
+            RecordingState::Error { message } => {
+                self.final_status("");
+                let _ = self.result_tx.send(Err(message));
+            }
+        }
+    }
+}
+
+/// Run the listen command: record from the input device until silence
+/// (or, in continuous mode, through pauses), Ctrl-C or the max duration,
+/// then print the transcription to stdout. With `save_audio`, the recorded
+/// audio is also saved (it always is if enabled in the config).
+pub async fn run_listen(
+    device: Option<String>,
+    channels: Vec<u16>,
+    language: Option<&str>,
+    max_duration: Option<u32>,
+    continuous: bool,
+    save_audio: bool,
+) -> anyhow::Result<()> {
+    let engine = DiktoEngine::new();
+    if !engine.is_model_available() {
+        let model_name = engine.get_config().model_name;
+        anyhow::bail!(
+            "Model '{model_name}' is not downloaded. Run: dikto --setup --model {model_name}"
+        );
+    }
+
+    let mut listen_config = ListenConfig::from(&engine.get_config());
+    if let Some(language) = language {
+        listen_config.language = language.to_string();
+    }
+    if let Some(max_duration) = max_duration {
+        listen_config.max_duration = max_duration;
+    }
+    listen_config.continuous = continuous;
+    listen_config.save_audio |= save_audio;
+
+    let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+    let callback = Arc::new(CliCallback {
+        result_tx,
+        continuous,
+        show_meter: std::io::stderr().is_terminal(),
+        line: Mutex::new((String::new(), String::new())),
+    });
+    let config = engine.get_config();
+    let input = AudioInput::Microphone(AudioCaptureConfig {
+        device_name: device.or(config.input_device),
+        input_channels: if channels.is_empty() {
+            config.input_channels
+        } else {
+            channels
+        },
+        resample_quality: config.resample_quality,
+        ..Default::default()
+    });
+    let handle = engine.start_listening_with_input(listen_config, input, callback)?;
+
+    let result = tokio::select! {
+        result = result_rx.recv() => result,
+        _ = tokio::signal::ctrl_c() => {
+            // Stop recording but still transcribe what was captured so far
+            handle.stop();
+            result_rx.recv().await
+        }
+    };
+
+    match result {
+        Some(Ok(text)) => {
+            // Continuous mode already printed each utterance
+            if !continuous && !text.is_empty() {
+                println!("{text}");
+            }
+            Ok(())
+        }
+        Some(Err(message)) => anyhow::bail!(message),
+        None => anyhow::bail!("Session ended without a result"),
+    }
+}
+
+/// Run the devices command: print the available input devices.
+pub fn run_devices() {
+    let devices = dikto_core::list_input_devices();
+    if devices.is_empty() {
+        eprintln!("No input devices found");
+        return;
+    }
+    for device in devices {
+        let marker = if device.is_default { " (default)" } else { "" };
+        println!(
+            "{}{marker} — {}Hz, {}ch",
+            device.name, device.default_sample_rate, device.channels
+        );
+    }
+}
@@ -1,6 +1,12 @@
+mod listen;
+mod models;
 mod setup;
+mod transcribe;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use dikto_cli::serve;
+use dikto_core::output::{OutputFormat, SubtitleOptions};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -17,10 +23,143 @@ struct Cli {
     /// Model to download (use with --setup). Default: parakeet-tdt-0.6b-v2
     #[arg(long)]
     model: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Transcribe an audio file (WAV, FLAC, MP3, OGG)
+    Transcribe {
+        /// Path to the audio file
+        file: PathBuf,
+
+        /// Model to use. Default: the configured model
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Language code (e.g. "en", or "auto" for Whisper). Default: the configured language
+        #[arg(long)]
+        language: Option<String>,
+
+        /// Output format: text, srt, vtt or json
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
+
+        /// Print the result as JSON (same as --format json)
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Maximum characters per subtitle line (srt/vtt)
+        #[arg(long, default_value_t = 42)]
+        max_line_length: u32,
+
+        /// Maximum subtitle cue duration in seconds (srt/vtt)
+        #[arg(long, default_value_t = 7.0)]
+        max_cue_duration: f64,
+    },
+    /// Dictate from the configured input device and print the text to stdout
+    Listen {
+        /// Input device name (see `dikto devices`). Default: the configured device
+        #[arg(long)]
+        device: Option<String>,
+
+        /// Input channel(s) to record, 1-based; several are averaged (e.g. `--channel 3`
+        /// or `--channel 1,2`). Default: the configured channels, or all
+        #[arg(long = "channel", value_delimiter = ',')]
+        channels: Vec<u16>,
+
+        /// Language code (e.g. "en", or "auto" for Whisper). Default: the configured language
+        #[arg(long)]
+        language: Option<String>,
+
+        /// Maximum recording duration in seconds. Default: the configured max duration
+        #[arg(long)]
+        max_duration: Option<u32>,
+
+        /// Keep listening through pauses, printing each utterance as it finishes;
+        /// stop with Ctrl-C or --max-duration
+        #[arg(long)]
+        continuous: bool,
+
+        /// Save the recorded audio as a WAV file in the data dir (see `save_audio`
+        /// in the config) and print its path
+        #[arg(long)]
+        save_audio: bool,
+    },
+    /// List audio input devices
+    Devices,
+    /// Manage models
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommand,
+    },
+    /// Serve an OpenAI-compatible transcription API (POST /v1/audio/transcriptions)
+    /// and live WebSocket transcription (GET /v1/audio/stream)
+    Serve {
+        /// Address to bind. Use 0.0.0.0 to accept connections from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// List known models: size, size on disk, download status; `*` marks the configured one
+    List,
+    /// Download a model, resuming an interrupted download
+    Download {
+        /// Model name (see `dikto models list`)
+        name: String,
+    },
+    /// Delete a downloaded model (not the configured one)
+    Delete {
+        /// Model name
+        name: String,
+    },
+    /// Show disk usage of downloaded models and leftover partial downloads
+    Du,
+    /// Install a model from files already on disk (no download)
+    Import {
+        /// Model name (built-in or from ~/.config/dikto/models.json)
+        name: String,
+
+        /// Directory with the model's files, or the ggml .bin of a Whisper model
+        path: PathBuf,
+
+        /// Symlink the files instead of copying them
+        #[arg(long)]
+        link: bool,
+    },
+    /// Fetch the model catalog: new models and updates of installed ones
+    Refresh {
+        /// Catalog URL (default: `catalog_url` from the config)
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Re-hash installed model files and report missing, corrupt and extra files
+    Verify {
+        /// Model to check (default: every installed model)
+        name: Option<String>,
+
+        /// Re-download missing and corrupt files
+        #[arg(long)]
+        repair: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
     let cli = Cli::parse();
 
     if cli.setup {
@@ -28,5 +167,78 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    match cli.command {
+        Some(Command::Transcribe {
+            file,
+            model,
+            language,
+            format,
+            json,
+            max_line_length,
+            max_cue_duration,
+        }) => {
+            let format = if json { OutputFormat::Json } else { format };
+            let options = SubtitleOptions {
+                max_line_length,
+                max_cue_duration_secs: max_cue_duration,
+            };
+            transcribe::run_transcribe(
+                &file,
+                model.as_deref(),
+                language.as_deref(),
+                format,
+                &options,
+            )?;
+        }
+        Some(Command::Listen {
+            device,
+            channels,
+            language,
+            max_duration,
+            continuous,
+            save_audio,
+        }) => {
+            listen::run_listen(
+                device,
+                channels,
+                language.as_deref(),
+                max_duration,
+                continuous,
+                save_audio,
+            )
+            .await?;
+        }
+        Some(Command::Devices) => {
+            listen::run_devices();
+        }
+        Some(Command::Models { command }) => match command {
+            ModelsCommand::List => {
+                models::run_list()?;
+            }
+            ModelsCommand::Download { name } => {
+                models::run_download(&name).await?;
+            }
+            ModelsCommand::Delete { name } => {
+                models::run_delete(&name)?;
+            }
+            ModelsCommand::Du => {
+                models::run_du()?;
+            }
+            ModelsCommand::Import { name, path, link } => {
+                models::run_import(&name, &path, link)?;
+            }
+            ModelsCommand::Refresh { url } => {
+                models::run_refresh(url.as_deref()).await?;
+            }
+            ModelsCommand::Verify { name, repair } => {
+                models::run_verify(name.as_deref(), repair).await?;
+            }
+        },
+        Some(Command::Serve { host, port }) => {
+            serve::run_serve(&host, port).await?;
+        }
+        None => {}
+    }
+
     Ok(())
 }
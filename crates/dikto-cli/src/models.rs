@@ -0,0 +1,335 @@
+use dikto_core::catalog;
+use dikto_core::config;
+use dikto_core::models::{self, DownloadOptions, ImportMode, ModelError, ModelInfo, VerifyReport};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+/// Progress bar for model downloads, in bytes.
+pub fn download_progress_bar(total_bytes: u64) -> indicatif::ProgressBar {
+    let bar = indicatif::ProgressBar::new(total_bytes);
+    bar.set_style(
+        indicatif::ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    bar
+}
+
+/// Download `model` with a progress bar. Ctrl-C cancels; the partial files
+/// are kept unless the config says otherwise. Returns false if cancelled.
+pub async fn download(model: &ModelInfo) -> anyhow::Result<bool> {
+    eprintln!(
+        "Downloading model '{}' (~{} MB, {} file{})...",
+        model.name,
+        model.size_mb,
+        model.files.len(),
+        if model.files.len() == 1 { "" } else { "s" }
+    );
+
+    let total_bytes: u64 = model
+        .files
+        .iter()
+        .map(|f| f.size_mb as u64 * 1024 * 1024)
+        .sum();
+    let bar = download_progress_bar(total_bytes);
+
+    let options = DownloadOptions::from_config(&config::load_config());
+    let cancel = options.cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            cancel.store(true, Ordering::Release);
+        }
+    });
+
+    let bar_clone = bar.clone();
+    let result = models::download_model_with_options(
+        model.name,
+        &options,
+        move |downloaded, total| {
+            if total > 0 {
+                bar_clone.set_length(total);
+            }
+            bar_clone.set_position(downloaded);
+        },
+        |_| {},
+    )
+    .await;
+    let path = match result {
+        Err(ModelError::Cancelled) => {
+            bar.abandon();
+            if options.keep_partial_on_cancel {
+                eprintln!("Download cancelled. Run the same command again to resume.");
+            } else {
+                eprintln!("Download cancelled.");
+            }
+            return Ok(false);
+        }
+        other => other?,
+    };
+
+    bar.finish_with_message("Download complete!");
+    eprintln!("Model saved to {}", path.display());
+    Ok(true)
+}
+
+/// Human-readable byte count, e.g. "460 MB" or "2.4 GB".
+fn format_bytes(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    const GB: u64 = 1024 * MB;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{} MB", bytes / MB)
+    } else if bytes > 0 {
+        format!("{} KB", bytes.div_ceil(1024))
+    } else {
+        "-".to_string()
+    }
+}
+
+/// Model by name, or an error listing the known models.
+fn find(name: &str) -> anyhow::Result<&'static ModelInfo> {
+    models::find_model(name).ok_or_else(|| {
+        let available: Vec<_> = models::all_models().map(|m| m.name).collect();
+        anyhow::anyhow!(
+            "Unknown model '{name}'. Available: {}",
+            available.join(", ")
+        )
+    })
+}
+
+/// Run `models list`: every known model with its status and size on disk.
+/// `*` marks the configured model.
+pub fn run_list() -> anyhow::Result<()> {
+    let configured = config::load_config().model_name;
+    println!("  {:<28} {:>9} {:>9}  STATUS", "NAME", "SIZE", "ON DISK");
+    for (model, downloaded) in models::list_models() {
+        let usage = models::model_disk_usage(model.name)?;
+        let status = if downloaded && models::update_available(model.name) {
+            "downloaded, update available".to_string()
+        } else if downloaded {
+            "downloaded".to_string()
+        } else if !usage.partial_files.is_empty() {
+            format!(
+                "partial download ({}, resumable)",
+                format_bytes(usage.partial_bytes)
+            )
+        } else {
+            "not downloaded".to_string()
+        };
+        let marker = if model.name == configured { "*" } else { " " };
+        println!(
+            "{marker} {:<28} {:>9} {:>9}  {status}",
+            model.name,
+            format_bytes(model.size_mb as u64 * 1024 * 1024),
+            format_bytes(usage.total_bytes),
+        );
+    }
+    Ok(())
+}
+
+/// Run `models download`: fetch a model (resuming partial files), or the
+/// changed files of an installed model with an update available.
+pub async fn run_download(name: &str) -> anyhow::Result<()> {
+    let model = find(name)?;
+    if models::is_model_downloaded(name) && !models::update_available(name) {
+        eprintln!("Model '{name}' already downloaded.");
+        return Ok(());
+    }
+    download(model).await?;
+    Ok(())
+}
+
+/// Run `models delete`: remove a downloaded model. The configured model is refused.
+pub fn run_delete(name: &str) -> anyhow::Result<()> {
+    let models_dir = config::models_dir();
+    let dir = models_dir.join(name);
+    if models::find_model(name).is_none() {
+        // Directories of models no longer known (listed by `models du`) can go too
+        if !models::is_plain_name(name) || !dir.is_dir() {
+            find(name)?;
+        }
+    } else if config::load_config().model_name == name {
+        anyhow::bail!(
+            "'{name}' is the configured model; switch to another model before deleting it"
+        );
+    }
+    let usage = models::dir_disk_usage(&dir)?;
+    if !models::delete_model_dir(&models_dir, name)? {
+        eprintln!("Model '{name}' is not downloaded.");
+        return Ok(());
+    }
+    // Linked files stay where they are
+    eprintln!(
+        "Deleted '{name}', freed {}.",
+        format_bytes(usage.total_bytes - usage.linked_bytes)
+    );
+    Ok(())
+}
+
+/// Run `models du`: disk usage per installed model, leftovers of interrupted
+/// downloads, and directories of models that are no longer known.
+pub fn run_du() -> anyhow::Result<()> {
+    let dir = config::models_dir();
+    let mut total = 0;
+    let mut partial_total = 0;
+
+    let mut entries: Vec<_> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    entries.sort();
+
+    for name in &entries {
+        let usage = models::dir_disk_usage(&dir.join(name))?;
+        total += usage.total_bytes;
+        partial_total += usage.partial_bytes;
+        let note = if models::find_model(name).is_none() {
+            "  (unknown model)"
+        } else {
+            ""
+        };
+        println!("{:>9}  {name}{note}", format_bytes(usage.total_bytes));
+        for file in &usage.partial_files {
+            println!("{:>9}    stale: {file}", "");
+        }
+    }
+
+    println!("{:>9}  total in {}", format_bytes(total), dir.display());
+    if partial_total > 0 {
+        println!(
+            "{:>9}  in partial files (resume with `dikto models download <name>`, or remove with `dikto models delete <name>`)",
+            format_bytes(partial_total)
+        );
+    }
+    Ok(())
+}
+
+/// Run `models refresh`: fetch the model catalog from `url` (default: the
+/// configured `catalog_url`) and report new models and available updates.
+pub async fn run_refresh(url: Option<&str>) -> anyhow::Result<()> {
+    let config = config::load_config();
+    let Some(url) = url
+        .map(str::to_string)
+        .or_else(|| config.catalog_url.clone())
+    else {
+        anyhow::bail!("No catalog configured; set `catalog_url` in the config or pass --url");
+    };
+    eprintln!("Fetching model catalog from {url}...");
+    let options = DownloadOptions::from_config(&config);
+    let catalog =
+        catalog::refresh_catalog(&url, config.catalog_public_key.as_deref(), &options).await?;
+    println!("Catalog: {} model(s)", catalog.len());
+
+    for model in &catalog {
+        if !models::MODELS.iter().any(|m| m.name == model.name) {
+            println!("  new:    {} ({} MB)", model.name, model.size_mb);
+        }
+    }
+    for (model, downloaded) in models::list_models() {
+        if downloaded && models::update_available(model.name) {
+            println!(
+                "  update: {} (apply with `dikto models download {}`)",
+                model.name, model.name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Run `models import`: install a model from local files instead of downloading it.
+pub fn run_import(name: &str, path: &Path, link: bool) -> anyhow::Result<()> {
+    let mode = if link {
+        ImportMode::Symlink
+    } else {
+        ImportMode::Copy
+    };
+    eprintln!(
+        "Verifying and importing '{name}' from {}...",
+        path.display()
+    );
+    let dir = models::import_model(name, path, mode)?;
+    eprintln!("Model '{name}' installed at {}", dir.display());
+    Ok(())
+}
+
+/// Run `models verify`: re-hash installed models (the given one, or every
+/// model with files on disk) and optionally re-download bad files.
+/// Fails if any model is still incomplete or corrupt.
+pub async fn run_verify(name: Option<&str>, repair: bool) -> anyhow::Result<()> {
+    let names: Vec<String> = match name {
+        Some(name) => vec![name.to_string()],
+        None => models::all_models()
+            .filter(|m| config::models_dir().join(m.name).exists())
+            .map(|m| m.name.to_string())
+            .collect(),
+    };
+    if names.is_empty() {
+        eprintln!("No models installed.");
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for name in &names {
+        eprintln!("Verifying '{name}'...");
+        let mut report = models::verify_model(name)?;
+        print_report(name, &report);
+
+        if repair && !report.is_ok() {
+            eprintln!("Repairing '{name}'...");
+            let bar = download_progress_bar(0);
+            let bar_clone = bar.clone();
+            let options = DownloadOptions::from_config(&config::load_config());
+            report = models::repair_model(name, &options, move |downloaded, total| {
+                bar_clone.set_length(total);
+                bar_clone.set_position(downloaded);
+            })
+            .await?;
+            bar.finish_and_clear();
+            print_report(name, &report);
+        }
+        if !report.is_ok() {
+            failed.push(name.as_str());
+        }
+    }
+
+    if !failed.is_empty() {
+        let hint = if repair {
+            ""
+        } else {
+            " (run with --repair to re-download bad files)"
+        };
+        anyhow::bail!("Verification failed for {}{hint}", failed.join(", "));
+    }
+    Ok(())
+}
+
+fn print_report(name: &str, report: &VerifyReport) {
+    if report.is_ok() {
+        println!("{name}: OK");
+    } else {
+        println!(
+            "{name}: {} missing, {} corrupt",
+            report.missing.len(),
+            report.corrupt.len()
+        );
+    }
+    for file in &report.missing {
+        println!("  missing:    {file}");
+    }
+    for file in &report.corrupt {
+        println!("  corrupt:    {file}");
+    }
+    for file in &report.unverified {
+        println!("  unverified: {file} (no known hash, size looks right)");
+    }
+    for file in &report.extra {
+        println!("  extra:      {file}");
+    }
+}
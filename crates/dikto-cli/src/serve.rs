@@ -0,0 +1,377 @@
+use crate::stream;
+use axum::extract::{DefaultBodyLimit, Multipart, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use dikto_core::config::{self, DiktoConfig};
+use dikto_core::decode::{self, TARGET_SAMPLE_RATE};
+use dikto_core::engine::{AsrSession, LoadedEngine};
+use dikto_core::models;
+use dikto_core::output::{self, SubtitleOptions};
+use dikto_core::transcribe::TranscriptSegment;
+use serde_json::json;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use tracing::{debug, info};
+
+/// Largest accepted upload (about an hour of uncompressed 48kHz stereo audio).
+const MAX_UPLOAD_BYTES: usize = 512 * 1024 * 1024;
+
+/// Model name sent by OpenAI clients; mapped to the configured model.
+const OPENAI_MODEL_ALIAS: &str = "whisper-1";
+
+/// State shared by all requests and streaming sessions.
+#[derive(Clone)]
+pub struct ServerState {
+    /// The loaded model, lazily (re)loaded when a request asks for another one.
+    pub engine: Arc<Mutex<Option<LoadedEngine>>>,
+    /// Serializes HTTP transcriptions.
+    busy: Arc<Mutex<()>>,
+    /// Model used by running requests and sessions; see `lease_model`.
+    users: Arc<Mutex<ModelUsers>>,
+    /// User config, for the default model, language and VAD settings.
+    pub config: DiktoConfig,
+}
+
+/// The model running requests and streaming sessions use, and how many
+/// there are. They share one loaded engine, so it may only switch to another
+/// model when none is running.
+#[derive(Debug, Default)]
+struct ModelUsers {
+    model: Option<String>,
+    count: usize,
+}
+
+/// A running request's or session's claim on the loaded model. While any
+/// lease is held, no other model can be loaded. Released on drop.
+#[derive(Debug)]
+pub struct ModelLease {
+    users: Arc<Mutex<ModelUsers>>,
+}
+
+impl Drop for ModelLease {
+    fn drop(&mut self) {
+        let mut users = self.users.lock().unwrap_or_else(PoisonError::into_inner);
+        users.count -= 1;
+    }
+}
+
+impl ServerState {
+    pub fn new(config: DiktoConfig) -> Self {
+        Self {
+            engine: Arc::new(Mutex::new(None)),
+            busy: Arc::new(Mutex::new(())),
+            users: Arc::new(Mutex::new(ModelUsers::default())),
+            config,
+        }
+    }
+
+    /// Model to use for a request's `model` field: the configured model for
+    /// none or the OpenAI alias, otherwise a registered, downloaded model.
+    pub fn resolve_model(&self, requested: Option<&str>) -> Result<String, String> {
+        let model_name = match requested {
+            None | Some("") | Some(OPENAI_MODEL_ALIAS) => self.config.model_name.clone(),
+            Some(name) => name.to_string(),
+        };
+        if models::find_model(&model_name).is_none() {
+            return Err(format!("Unknown model '{model_name}'"));
+        }
+        if !models::is_model_downloaded(&model_name) {
+            return Err(format!(
+                "Model '{model_name}' is not downloaded. Download it with: dikto --setup --model {model_name}"
+            ));
+        }
+        Ok(model_name)
+    }
+
+    /// Claim `model_name` for a request or session, before loading it. Fails
+    /// while requests or sessions using another model are running, since
+    /// loading it would swap the engine under them.
+    pub fn lease_model(&self, model_name: &str) -> Result<ModelLease, String> {
+        let mut users = self.users.lock().unwrap_or_else(PoisonError::into_inner);
+        match &users.model {
+            Some(current) if users.count > 0 && current != model_name => {
+                return Err(format!(
+                    "Model '{current}' is in use by another session; '{model_name}' can be used when it has finished"
+                ));
+            }
+            _ => {}
+        }
+        users.model = Some(model_name.to_string());
+        users.count += 1;
+        Ok(ModelLease {
+            users: self.users.clone(),
+        })
+    }
+}
+
+/// An error returned in the OpenAI error shape.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let kind = if self.status.is_client_error() {
+            "invalid_request_error"
+        } else {
+            "server_error"
+        };
+        let body = json!({
+            "error": {
+                "message": self.message,
+                "type": kind,
+            }
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// `response_format` values of the OpenAI transcription API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseFormat {
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+impl std::str::FromStr for ResponseFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "srt" => Ok(Self::Srt),
+            "vtt" => Ok(Self::Vtt),
+            "verbose_json" => Ok(Self::VerboseJson),
+            other => Err(format!(
+                "Unsupported response_format '{other}' (expected json, text, srt, vtt or verbose_json)"
+            )),
+        }
+    }
+}
+
+/// Parsed multipart form of a transcription request.
+struct TranscriptionRequest {
+    audio: Vec<u8>,
+    extension: Option<String>,
+    model: Option<String>,
+    language: Option<String>,
+    format: ResponseFormat,
+}
+
+/// Run the serve command: an OpenAI-compatible transcription API and a
+/// WebSocket streaming endpoint on `host:port`.
+pub async fn run_serve(host: &str, port: u16) -> anyhow::Result<()> {
+    let state = ServerState::new(config::load_config());
+
+    let app = Router::new()
+        .route("/v1/audio/transcriptions", post(transcriptions))
+        .route("/v1/audio/stream", get(stream::stream))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind((host, port)).await?;
+    let addr = listener.local_addr()?;
+    eprintln!("Listening on http://{addr}/v1/audio/transcriptions");
+    eprintln!("Streaming on ws://{addr}/v1/audio/stream");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+/// `POST /v1/audio/transcriptions`
+async fn transcriptions(
+    State(state): State<ServerState>,
+    multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let request = parse_request(multipart).await?;
+
+    let model_name = state
+        .resolve_model(request.model.as_deref())
+        .map_err(ApiError::bad_request)?;
+    let lease = state.lease_model(&model_name).map_err(ApiError::conflict)?;
+    let language = request
+        .language
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| state.config.language.clone());
+
+    let format = request.format;
+    let task_language = language.clone();
+    let (segments, duration) = tokio::task::spawn_blocking(move || {
+        let _lease = lease;
+        transcribe(
+            &state,
+            &model_name,
+            language,
+            request.audio,
+            request.extension.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Transcription task failed: {e}")))??;
+
+    Ok(render(&segments, format, &task_language, duration))
+}
+
+/// Read the multipart form fields; unknown fields (prompt, temperature, ...) are ignored.
+async fn parse_request(mut multipart: Multipart) -> Result<TranscriptionRequest, ApiError> {
+    let mut audio = None;
+    let mut extension = None;
+    let mut model = None;
+    let mut language = None;
+    let mut format = ResponseFormat::Json;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::bad_request(format!("Invalid multipart body: {e}")))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "file" => {
+                extension = field
+                    .file_name()
+                    .and_then(|f| Path::new(f).extension())
+                    .and_then(|e| e.to_str())
+                    .map(str::to_lowercase);
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::bad_request(format!("Failed to read file: {e}")))?;
+                audio = Some(bytes.to_vec());
+            }
+            "model" | "language" | "response_format" => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::bad_request(format!("Invalid '{name}' field: {e}")))?;
+                match name.as_str() {
+                    "model" => model = Some(value),
+                    "language" => language = Some(value),
+                    _ => format = value.parse().map_err(ApiError::bad_request)?,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let audio = audio.ok_or_else(|| ApiError::bad_request("Missing 'file' field"))?;
+    Ok(TranscriptionRequest {
+        audio,
+        extension,
+        model,
+        language,
+        format,
+    })
+}
+
+/// Decode and transcribe an upload with `model_name`, loading it if needed.
+/// Returns the segments and the audio duration in seconds.
+fn transcribe(
+    state: &ServerState,
+    model_name: &str,
+    language: String,
+    audio: Vec<u8>,
+    extension: Option<&str>,
+) -> Result<(Vec<TranscriptSegment>, f64), ApiError> {
+    let samples =
+        decode::decode_bytes(audio, extension).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let duration = samples.len() as f64 / TARGET_SAMPLE_RATE as f64;
+
+    let _busy = state
+        .busy
+        .lock()
+        .map_err(|e| ApiError::internal(format!("Lock poisoned: {e}")))?;
+    dikto_core::ensure_engine_loaded(&state.engine, model_name, || {
+        info!("Loading model '{model_name}'...");
+    })
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    debug!("Transcribing {duration:.1}s of audio with '{model_name}'");
+    let mut session = AsrSession::new(language);
+    session.feed_samples(&samples);
+    let segments = session
+        .flush(&state.engine)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok((segments, duration))
+}
+
+/// Build the response body for `format`.
+pub fn render(
+    segments: &[TranscriptSegment],
+    format: ResponseFormat,
+    language: &str,
+    duration: f64,
+) -> Response {
+    let options = SubtitleOptions::default();
+    match format {
+        ResponseFormat::Json => Json(json!({ "text": output::to_text(segments) })).into_response(),
+        ResponseFormat::Text => plain("text/plain; charset=utf-8", output::to_text(segments)),
+        ResponseFormat::Srt => plain(
+            "text/plain; charset=utf-8",
+            output::to_srt(segments, &options),
+        ),
+        ResponseFormat::Vtt => plain(
+            "text/vtt; charset=utf-8",
+            output::to_vtt(segments, &options),
+        ),
+        ResponseFormat::VerboseJson => {
+            let words: Vec<_> = segments
+                .iter()
+                .flat_map(|s| &s.words)
+                .map(|w| json!({ "word": w.text, "start": w.start, "end": w.end }))
+                .collect();
+            let segments_json: Vec<_> = segments
+                .iter()
+                .enumerate()
+                .map(|(id, s)| json!({ "id": id, "start": s.start, "end": s.end, "text": s.text }))
+                .collect();
+            Json(json!({
+                "task": "transcribe",
+                "language": language,
+                "duration": duration,
+                "text": output::to_text(segments),
+                "segments": segments_json,
+                "words": words,
+            }))
+            .into_response()
+        }
+    }
+}
+
+fn plain(content_type: &'static str, body: String) -> Response {
+    ([(header::CONTENT_TYPE, content_type)], body).into_response()
+}
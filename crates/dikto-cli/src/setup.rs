@@ -45,6 +45,12 @@ pub async fn run_setup(model_name: Option<&str>) -> anyhow::Result<()> {
                     m.name, m.size_mb, m.description, status
                 );
             }
+            // A broken manifest is otherwise only logged; explain why custom models are missing
+            if let Ok(manifest) = config::models_manifest_path() {
+                if let Err(e) = models::load_manifest(&manifest) {
+                    eprintln!("\n{}: {e}", manifest.display());
+                }
+            }
             anyhow::bail!("Invalid model name: {model_name}");
         }
     };
@@ -52,38 +58,8 @@ pub async fn run_setup(model_name: Option<&str>) -> anyhow::Result<()> {
     // Download model if not present
     if models::is_model_downloaded(model_name) {
         eprintln!("Model '{model_name}' already downloaded.");
-    } else {
-        eprintln!(
-            "Downloading model '{model_name}' (~{} MB, {} file{})...",
-            model.size_mb,
-            model.files.len(),
-            if model.files.len() == 1 { "" } else { "s" }
-        );
-
-        let total_bytes: u64 = model
-            .files
-            .iter()
-            .map(|f| f.size_mb as u64 * 1024 * 1024)
-            .sum();
-        let bar = indicatif::ProgressBar::new(total_bytes);
-        bar.set_style(
-            indicatif::ProgressStyle::default_bar()
-                .template("[{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                .unwrap()
-                .progress_chars("=> "),
-        );
-
-        let bar_clone = bar.clone();
-        let path = models::download_model(model_name, move |downloaded, total| {
-            if total > 0 {
-                bar_clone.set_length(total);
-            }
-            bar_clone.set_position(downloaded);
-        })
-        .await?;
-
-        bar.finish_with_message("Download complete!");
-        eprintln!("Model saved to {}", path.display());
+    } else if !crate::models::download(model).await? {
+        return Ok(());
     }
 
     eprintln!("\nSetup complete! You can now use dikto.");
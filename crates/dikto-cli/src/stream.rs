@@ -0,0 +1,276 @@
+use crate::serve::ServerState;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use dikto_core::config::{DiktoConfig, MAX_DURATION_SECS};
+use dikto_core::level::InputLevel;
+use dikto_core::source::{AudioInput, ChannelSource, PcmDecoder, PcmFormat};
+use dikto_core::transcribe::TranscriptSegment;
+use dikto_core::{ListenConfig, RecordingState, TranscriptionCallback};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Query parameters of `GET /v1/audio/stream`.
+#[derive(Debug, Default, Deserialize)]
+pub struct StreamParams {
+    /// Audio frame encoding: "f32" (default), "s16le" or "opus".
+    format: Option<String>,
+    model: Option<String>,
+    language: Option<String>,
+    /// Keep transcribing through pauses (default true). With false, the
+    /// session ends at the first silence, like a single dictation.
+    continuous: Option<bool>,
+    /// Maximum session length in seconds. Default: the configured max duration.
+    max_duration: Option<u32>,
+    /// `false` doesn't save the session audio even if the config's
+    /// `save_audio` is on. Clients can't turn saving on: it writes to the
+    /// server's data dir.
+    save_audio: Option<bool>,
+}
+
+impl StreamParams {
+    /// Session settings: the config, overridden by the query parameters.
+    pub fn listen_config(&self, config: &DiktoConfig) -> ListenConfig {
+        let mut listen_config = ListenConfig::from(config);
+        if let Some(language) = self.language.as_ref().filter(|l| !l.is_empty()) {
+            listen_config.language = language.clone();
+        }
+        if let Some(max_duration) = self.max_duration {
+            listen_config.max_duration = max_duration.clamp(1, MAX_DURATION_SECS);
+        }
+        listen_config.continuous = self.continuous.unwrap_or(true);
+        if self.save_audio == Some(false) {
+            listen_config.save_audio = false;
+        }
+        listen_config
+    }
+}
+
+/// How binary frames from the client are turned into samples.
+pub enum FrameDecoder {
+    Pcm(PcmDecoder),
+    #[cfg(feature = "opus")]
+    Opus(audiopus::coder::Decoder),
+}
+
+impl FrameDecoder {
+    pub fn new(format: &str) -> Result<Self, String> {
+        match format {
+            "f32" | "f32le" => Ok(Self::Pcm(PcmDecoder::new(PcmFormat::F32Le))),
+            "s16" | "s16le" => Ok(Self::Pcm(PcmDecoder::new(PcmFormat::S16Le))),
+            #[cfg(feature = "opus")]
+            "opus" => audiopus::coder::Decoder::new(
+                audiopus::SampleRate::Hz16000,
+                audiopus::Channels::Mono,
+            )
+            .map(Self::Opus)
+            .map_err(|e| format!("Failed to create Opus decoder: {e}")),
+            #[cfg(not(feature = "opus"))]
+            "opus" => Err("Opus support is not enabled in this build".to_string()),
+            other => Err(format!(
+                "Unsupported format '{other}' (expected f32, s16le or opus)"
+            )),
+        }
+    }
+
+    /// Decode one binary frame. PCM may be split anywhere; each Opus frame
+    /// must hold exactly one packet.
+    pub fn decode(&mut self, frame: &[u8]) -> Result<Vec<f32>, String> {
+        match self {
+            Self::Pcm(decoder) => Ok(decoder.push(frame)),
+            #[cfg(feature = "opus")]
+            Self::Opus(decoder) => {
+                // Longest Opus packet: 120ms at 16kHz
+                let mut out = vec![0.0f32; 1920];
+                let packet = frame
+                    .try_into()
+                    .map_err(|e| format!("Bad Opus packet: {e}"))?;
+                let signals = (&mut out[..]).try_into().map_err(|e| format!("{e}"))?;
+                let n = decoder
+                    .decode_float(Some(packet), signals, false)
+                    .map_err(|e| format!("Opus decode failed: {e}"))?;
+                out.truncate(n);
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// Forwards pipeline events to the connection as JSON messages.
+pub struct WsCallback {
+    events: mpsc::UnboundedSender<Value>,
+}
+
+impl WsCallback {
+    pub fn new(events: mpsc::UnboundedSender<Value>) -> Self {
+        Self { events }
+    }
+
+    fn send(&self, event: Value) {
+        let _ = self.events.send(event);
+    }
+}
+
+impl TranscriptionCallback for WsCallback {
+    fn on_partial(&self, text: String) {
+        self.send(json!({ "type": "partial", "text": text }));
+    }
+
+    fn on_final_segment(&self, segment: TranscriptSegment) {
+        self.send(json!({ "type": "final_segment", "segment": segment_json(&segment) }));
+    }
+
+    fn on_silence(&self) {
+        self.send(json!({ "type": "silence" }));
+    }
+
+    fn on_error(&self, error: String) {
+        self.send(json!({ "type": "error", "message": error }));
+    }
+
+    fn on_warning(&self, message: String) {
+        self.send(json!({ "type": "warning", "message": message }));
+    }
+
+    fn on_level(&self, level: InputLevel) {
+        self.send(json!({
+            "type": "level",
+            "rms_dbfs": level.rms_dbfs,
+            "peak_dbfs": level.peak_dbfs,
+            "speech_probability": level.speech_probability,
+        }));
+    }
+
+    fn on_state_change(&self, state: RecordingState) {
+        let event = match state {
+            RecordingState::Listening => json!({ "type": "state", "state": "listening" }),
+            RecordingState::Processing => json!({ "type": "state", "state": "processing" }),
+            RecordingState::Done { text, audio_path } => {
+                json!({ "type": "state", "state": "done", "text": text, "audio_path": audio_path })
+            }
+            RecordingState::Error { message } => {
+                json!({ "type": "state", "state": "error", "message": message })
+            }
+        };
+        self.send(event);
+    }
+}
+
+fn segment_json(segment: &TranscriptSegment) -> Value {
+    let words: Vec<Value> = segment
+        .words
+        .iter()
+        .map(|w| json!({ "text": w.text, "start": w.start, "end": w.end }))
+        .collect();
+    json!({
+        "text": segment.text,
+        "start": segment.start,
+        "end": segment.end,
+        "words": words,
+    })
+}
+
+/// `GET /v1/audio/stream` — upgrade to a WebSocket transcription session.
+pub async fn stream(
+    State(state): State<ServerState>,
+    Query(params): Query<StreamParams>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| run_session(socket, state, params))
+}
+
+/// Run one streaming session: binary frames are audio, a text frame
+/// `{"type":"stop"}` (or closing the socket) ends the input. The socket is
+/// closed after the final `done` or `error` state event.
+async fn run_session(mut socket: WebSocket, state: ServerState, params: StreamParams) {
+    // The lease keeps other requests from switching the model until the session ends
+    let setup = FrameDecoder::new(params.format.as_deref().unwrap_or("f32")).and_then(|decoder| {
+        let model_name = state.resolve_model(params.model.as_deref())?;
+        let lease = state.lease_model(&model_name)?;
+        Ok((decoder, model_name, lease))
+    });
+    let (mut decoder, model_name, _lease) = match setup {
+        Ok(setup) => setup,
+        Err(message) => {
+            let event = json!({ "type": "error", "message": message });
+            let _ = socket.send(Message::Text(event.to_string().into())).await;
+            let _ = socket.send(Message::Close(None)).await;
+            return;
+        }
+    };
+
+    let listen_config = params.listen_config(&state.config);
+
+    let (audio_tx, source) = ChannelSource::new();
+    let mut audio_tx: Option<Sender<Vec<f32>>> = Some(audio_tx);
+    let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+    let handle = dikto_core::spawn_session(
+        state.engine.clone(),
+        model_name,
+        listen_config,
+        AudioInput::Custom(Box::new(move || Ok(Box::new(source)))),
+        Arc::new(WsCallback::new(events_tx)),
+        Arc::new(AtomicBool::new(false)),
+    );
+    debug!("WebSocket session started");
+
+    let mut client_open = true;
+    loop {
+        tokio::select! {
+            message = socket.recv(), if client_open => match message {
+                Some(Ok(Message::Binary(frame))) => {
+                    let Some(tx) = &audio_tx else { continue };
+                    match decoder.decode(&frame) {
+                        Ok(samples) if !samples.is_empty() => {
+                            let _ = tx.send(samples);
+                        }
+                        Ok(_) => {}
+                        Err(message) => {
+                            warn!("{message}");
+                            let event = json!({ "type": "error", "message": message });
+                            let _ = socket.send(Message::Text(event.to_string().into())).await;
+                        }
+                    }
+                }
+                Some(Ok(Message::Text(text))) => {
+                    let is_stop = serde_json::from_str::<Value>(&text)
+                        .map(|v| v["type"] == "stop")
+                        .unwrap_or(false);
+                    if is_stop {
+                        // End of input: the session transcribes what it has and finishes
+                        audio_tx = None;
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => {
+                    // Client went away: stop without waiting for more audio
+                    client_open = false;
+                    audio_tx = None;
+                    handle.stop();
+                }
+            },
+            event = events_rx.recv() => {
+                let Some(event) = event else { break };
+                let finished = event["type"] == "state"
+                    && (event["state"] == "done" || event["state"] == "error");
+                if client_open {
+                    let _ = socket.send(Message::Text(event.to_string().into())).await;
+                }
+                if finished {
+                    break;
+                }
+            }
+        }
+    }
+
+    handle.stop();
+    if client_open {
+        let _ = socket.send(Message::Close(None)).await;
+    }
+    debug!("WebSocket session ended");
+}
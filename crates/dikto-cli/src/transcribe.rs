@@ -0,0 +1,49 @@
+use dikto_core::config;
+use dikto_core::decode::{self, TARGET_SAMPLE_RATE};
+use dikto_core::engine::LoadedEngine;
+use dikto_core::output::{self, OutputFormat, SubtitleOptions};
+use dikto_core::transcribe::TranscribeConfig;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Run the transcribe command: decode an audio file and print its transcription
+/// as text, subtitles or JSON. Model and language default to the values in the user's config.
+pub fn run_transcribe(
+    file: &Path,
+    model_name: Option<&str>,
+    language: Option<&str>,
+    format: OutputFormat,
+    options: &SubtitleOptions,
+) -> anyhow::Result<()> {
+    let cfg = config::load_config();
+    let model_name = model_name.unwrap_or(&cfg.model_name).to_string();
+    let language = language.unwrap_or(&cfg.language).to_string();
+
+    eprintln!("Decoding {}...", file.display());
+    let samples = decode::decode_file(file)?;
+    let duration_secs = samples.len() as f32 / TARGET_SAMPLE_RATE as f32;
+
+    eprintln!("Loading model '{model_name}'...");
+    let loaded = dikto_core::load_engine(&model_name).map_err(|e| {
+        anyhow::anyhow!("{e}\nDownload it with: dikto --setup --model {model_name}")
+    })?;
+
+    let mut session = loaded.engine.create_session(TranscribeConfig {
+        language: language.clone(),
+    });
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(Some(loaded)));
+
+    eprintln!("Transcribing {duration_secs:.1}s of audio...");
+    session.feed_samples(&samples);
+    let segments = session.flush(&engine)?;
+
+    let output = output::format_transcript(&segments, format, &language, &model_name, options);
+    if format == OutputFormat::Srt || format == OutputFormat::Vtt {
+        // Subtitle formats already end with a blank line
+        print!("{output}");
+    } else {
+        println!("{output}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+// Tests for dikto_cli::serve — model resolution, response formats and the
+// OpenAI error shape, and model leases. Models are "downloaded" into a temporary home
+// directory; no model is ever loaded.
+
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use dikto_cli::serve::{render, ApiError, ResponseFormat, ServerState};
+use dikto_core::config::DiktoConfig;
+use dikto_core::transcribe::{TranscriptSegment, WordTimestamp};
+use serde_json::Value;
+use std::sync::Once;
+
+/// Point the home directory at a temp dir with whisper-tiny "downloaded".
+/// Shared by all tests of this file (they run in one process).
+fn init_home() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let home = std::env::temp_dir().join("dikto_serve_tests_home");
+        let _ = std::fs::remove_dir_all(&home);
+        let model_dir = home.join(".local/share/dikto/models/whisper-tiny");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        std::fs::write(model_dir.join("ggml-tiny.bin"), b"not a real model").unwrap();
+        std::env::set_var("HOME", &home);
+    });
+}
+
+fn state(model_name: &str) -> ServerState {
+    init_home();
+    ServerState::new(DiktoConfig {
+        model_name: model_name.to_string(),
+        ..DiktoConfig::default()
+    })
+}
+
+fn segments() -> Vec<TranscriptSegment> {
+    vec![
+        TranscriptSegment::from_words(vec![
+            WordTimestamp {
+                text: "Hello".to_string(),
+                start: 0.0,
+                end: 0.4,
+            },
+            WordTimestamp {
+                text: "world.".to_string(),
+                start: 0.5,
+                end: 1.0,
+            },
+        ]),
+        TranscriptSegment::from_words(vec![WordTimestamp {
+            text: "Again.".to_string(),
+            start: 2.0,
+            end: 2.5,
+        }]),
+    ]
+}
+
+async fn body(response: Response) -> (StatusCode, String, String) {
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// resolve_model
+// ---------------------------------------------------------------------------
+
+/// No model, an empty one and the OpenAI alias all mean the configured model.
+#[test]
+fn resolve_model_defaults_and_alias() {
+    let state = state("whisper-tiny");
+    for requested in [None, Some(""), Some("whisper-1")] {
+        assert_eq!(state.resolve_model(requested).unwrap(), "whisper-tiny");
+    }
+}
+
+/// A downloaded model other than the configured one can be requested by name.
+#[test]
+fn resolve_model_by_name() {
+    let state = state("parakeet-tdt-0.6b-v2");
+    assert_eq!(
+        state.resolve_model(Some("whisper-tiny")).unwrap(),
+        "whisper-tiny"
+    );
+}
+
+/// Unknown names are rejected.
+#[test]
+fn resolve_model_unknown() {
+    let err = state("whisper-tiny")
+        .resolve_model(Some("no-such-model"))
+        .unwrap_err();
+    assert!(err.contains("Unknown model 'no-such-model'"), "{err}");
+}
+
+/// Known but missing models are rejected with a hint to download them.
+#[test]
+fn resolve_model_not_downloaded() {
+    let err = state("whisper-tiny")
+        .resolve_model(Some("whisper-small"))
+        .unwrap_err();
+    assert!(err.contains("not downloaded"), "{err}");
+    assert!(err.contains("dikto --setup --model whisper-small"), "{err}");
+
+    // The alias doesn't hide a missing configured model
+    let err = state("whisper-small")
+        .resolve_model(Some("whisper-1"))
+        .unwrap_err();
+    assert!(err.contains("not downloaded"), "{err}");
+}
+
+// ---------------------------------------------------------------------------
+// Model leases
+// ---------------------------------------------------------------------------
+
+/// While a session uses one model, a request for another one is refused
+/// instead of swapping the shared engine under the running session.
+#[test]
+fn lease_blocks_model_switch_while_in_use() {
+    let state = state("whisper-tiny");
+    let session = state.lease_model("whisper-tiny").unwrap();
+    let other = state.clone();
+
+    let switch = std::thread::spawn(move || other.lease_model("whisper-small").map(drop))
+        .join()
+        .unwrap();
+    let err = switch.unwrap_err();
+    assert!(err.contains("'whisper-tiny' is in use"), "{err}");
+
+    // The same model can be shared
+    let second = state.lease_model("whisper-tiny").unwrap();
+    drop(session);
+    assert!(state.lease_model("whisper-small").is_err());
+
+    // Once all users are done, the model may change
+    drop(second);
+    let switched = state.lease_model("whisper-small").unwrap();
+    assert!(state.lease_model("whisper-tiny").is_err());
+    drop(switched);
+}
+
+/// The conflict is reported as a 409 in the OpenAI error shape.
+#[tokio::test]
+async fn api_error_conflict() {
+    let (status, _, body) = body(ApiError::conflict("in use").into_response()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let value: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(value["error"]["type"], "invalid_request_error");
+}
+
+// ---------------------------------------------------------------------------
+// ResponseFormat
+// ---------------------------------------------------------------------------
+
+/// All OpenAI response formats parse; anything else is an error naming them.
+#[test]
+fn response_format_parse() {
+    assert_eq!("json".parse(), Ok(ResponseFormat::Json));
+    assert_eq!("text".parse(), Ok(ResponseFormat::Text));
+    assert_eq!("srt".parse(), Ok(ResponseFormat::Srt));
+    assert_eq!("vtt".parse(), Ok(ResponseFormat::Vtt));
+    assert_eq!("verbose_json".parse(), Ok(ResponseFormat::VerboseJson));
+
+    let err = "xml".parse::<ResponseFormat>().unwrap_err();
+    assert!(err.contains("'xml'"), "{err}");
+    assert!(err.contains("verbose_json"), "{err}");
+    assert!("JSON".parse::<ResponseFormat>().is_err());
+}
+
+// ---------------------------------------------------------------------------
+// render
+// ---------------------------------------------------------------------------
+
+/// json: only the text.
+#[tokio::test]
+async fn render_json() {
+    let (status, content_type, body) =
+        body(render(&segments(), ResponseFormat::Json, "en", 2.5)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("application/json"));
+    let value: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(value, serde_json::json!({ "text": "Hello world. Again." }));
+}
+
+/// text: the plain transcript.
+#[tokio::test]
+async fn render_text() {
+    let (_, content_type, body) = body(render(&segments(), ResponseFormat::Text, "en", 2.5)).await;
+    assert!(content_type.starts_with("text/plain"));
+    assert_eq!(body.trim_end(), "Hello world. Again.");
+}
+
+/// srt: numbered cues with comma decimal separators.
+#[tokio::test]
+async fn render_srt() {
+    let (_, content_type, body) = body(render(&segments(), ResponseFormat::Srt, "en", 2.5)).await;
+    assert!(content_type.starts_with("text/plain"));
+    assert!(body.starts_with("1\n00:00:00,000 --> 00:00:01,000\nHello world.\n"));
+    assert!(body.contains("2\n00:00:02,000 --> 00:00:02,500\nAgain.\n"));
+}
+
+/// vtt: WEBVTT header and dot decimal separators.
+#[tokio::test]
+async fn render_vtt() {
+    let (_, content_type, body) = body(render(&segments(), ResponseFormat::Vtt, "en", 2.5)).await;
+    assert!(content_type.starts_with("text/vtt"));
+    assert!(body.starts_with("WEBVTT"));
+    assert!(body.contains("00:00:00.000 --> 00:00:01.000\nHello world."));
+}
+
+/// verbose_json: language, duration, numbered segments and all words.
+#[tokio::test]
+async fn render_verbose_json() {
+    let (_, _, body) = body(render(&segments(), ResponseFormat::VerboseJson, "fr", 2.5)).await;
+    let value: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(value["task"], "transcribe");
+    assert_eq!(value["language"], "fr");
+    assert_eq!(value["duration"], 2.5);
+    assert_eq!(value["text"], "Hello world. Again.");
+    assert_eq!(value["segments"][1]["id"], 1);
+    assert_eq!(value["segments"][1]["start"], 2.0);
+    assert_eq!(value["segments"][1]["text"], "Again.");
+    let words = value["words"].as_array().unwrap();
+    assert_eq!(words.len(), 3);
+    assert_eq!(
+        words[1],
+        serde_json::json!({ "word": "world.", "start": 0.5, "end": 1.0 })
+    );
+}
+
+// ---------------------------------------------------------------------------
+// ApiError
+// ---------------------------------------------------------------------------
+
+/// Client errors are 400 with type invalid_request_error.
+#[tokio::test]
+async fn api_error_bad_request() {
+    let response = ApiError::bad_request("Missing 'file' field").into_response();
+    let (status, content_type, body) = body(response).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(content_type.starts_with("application/json"));
+    let value: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "error": { "message": "Missing 'file' field", "type": "invalid_request_error" }
+        })
+    );
+}
+
+/// Server errors are 500 with type server_error.
+#[tokio::test]
+async fn api_error_internal() {
+    let (status, _, body) = body(ApiError::internal("boom").into_response()).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    let value: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(value["error"]["type"], "server_error");
+    assert_eq!(value["error"]["message"], "boom");
+}
@@ -0,0 +1,231 @@
+// Tests for dikto_cli::stream — binary frame decoding, query parameter
+// defaults and the JSON events sent to WebSocket clients.
+
+use dikto_cli::stream::{FrameDecoder, StreamParams, WsCallback};
+use dikto_core::config::{DiktoConfig, MAX_DURATION_SECS};
+use dikto_core::level::InputLevel;
+use dikto_core::transcribe::{TranscriptSegment, WordTimestamp};
+use dikto_core::{RecordingState, TranscriptionCallback};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+fn params(query: Value) -> StreamParams {
+    serde_json::from_value(query).unwrap()
+}
+
+/// Run `f` on a WsCallback and return the events it sent.
+fn events(f: impl FnOnce(&WsCallback)) -> Vec<Value> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    f(&WsCallback::new(tx));
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+// ---------------------------------------------------------------------------
+// FrameDecoder
+// ---------------------------------------------------------------------------
+
+/// f32 frames are little-endian floats.
+#[test]
+fn decode_f32_frames() {
+    let mut decoder = FrameDecoder::new("f32").unwrap();
+    let bytes: Vec<u8> = [0.5f32, -0.25]
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    assert_eq!(decoder.decode(&bytes).unwrap(), vec![0.5, -0.25]);
+}
+
+/// s16le frames are scaled to -1.0..1.0.
+#[test]
+fn decode_s16le_frames() {
+    let mut decoder = FrameDecoder::new("s16le").unwrap();
+    let bytes: Vec<u8> = [i16::MAX, 0, -i16::MAX]
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    assert_eq!(decoder.decode(&bytes).unwrap(), vec![1.0, 0.0, -1.0]);
+}
+
+/// Samples split across frames (odd lengths, misaligned) are carried over.
+#[test]
+fn decode_misaligned_frames() {
+    let mut decoder = FrameDecoder::new("s16le").unwrap();
+    let bytes: Vec<u8> = [100i16, 200, 300]
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    assert!(decoder.decode(&bytes[..1]).unwrap().is_empty());
+    assert_eq!(decoder.decode(&bytes[1..3]).unwrap().len(), 1);
+    assert_eq!(decoder.decode(&bytes[3..]).unwrap().len(), 2);
+
+    let mut decoder = FrameDecoder::new("f32").unwrap();
+    let bytes: Vec<u8> = [0.1f32, 0.2].iter().flat_map(|s| s.to_le_bytes()).collect();
+    assert!(decoder.decode(&bytes[..3]).unwrap().is_empty());
+    assert_eq!(decoder.decode(&bytes[3..7]).unwrap(), vec![0.1]);
+    assert_eq!(decoder.decode(&bytes[7..]).unwrap(), vec![0.2]);
+}
+
+/// Unknown formats are rejected with the supported ones listed.
+#[test]
+fn unknown_format_is_error() {
+    let err = FrameDecoder::new("mp3").err().unwrap();
+    assert!(err.contains("'mp3'"), "{err}");
+    assert!(err.contains("s16le"), "{err}");
+}
+
+// ---------------------------------------------------------------------------
+// StreamParams
+// ---------------------------------------------------------------------------
+
+/// Without parameters, sessions are continuous and use the config.
+#[test]
+fn params_defaults() {
+    let config = DiktoConfig {
+        language: "de".to_string(),
+        max_duration: 120,
+        ..DiktoConfig::default()
+    };
+    let listen_config = params(json!({})).listen_config(&config);
+    assert!(listen_config.continuous);
+    assert_eq!(listen_config.language, "de");
+    assert_eq!(listen_config.max_duration, 120);
+    assert!(!listen_config.save_audio);
+}
+
+/// Query parameters override the config; an empty language is ignored.
+#[test]
+fn params_override_config() {
+    let config = DiktoConfig::default();
+    let listen_config =
+        params(json!({ "language": "fr", "continuous": false, "max_duration": 60 }))
+            .listen_config(&config);
+    assert_eq!(listen_config.language, "fr");
+    assert!(!listen_config.continuous);
+    assert_eq!(listen_config.max_duration, 60);
+
+    let listen_config = params(json!({ "language": "" })).listen_config(&config);
+    assert_eq!(listen_config.language, config.language);
+}
+
+/// max_duration is clamped like the config value.
+#[test]
+fn params_clamp_max_duration() {
+    let config = DiktoConfig::default();
+    let listen_config = params(json!({ "max_duration": 999_999 })).listen_config(&config);
+    assert_eq!(listen_config.max_duration, MAX_DURATION_SECS);
+    let listen_config = params(json!({ "max_duration": 0 })).listen_config(&config);
+    assert_eq!(listen_config.max_duration, 1);
+}
+
+/// Clients may turn off saving audio, but not turn it on.
+#[test]
+fn params_save_audio_only_turns_off() {
+    let config = DiktoConfig::default();
+    let listen_config = params(json!({ "save_audio": true })).listen_config(&config);
+    assert!(!listen_config.save_audio);
+
+    let config = DiktoConfig {
+        save_audio: true,
+        ..DiktoConfig::default()
+    };
+    assert!(params(json!({})).listen_config(&config).save_audio);
+    let listen_config = params(json!({ "save_audio": false })).listen_config(&config);
+    assert!(!listen_config.save_audio);
+}
+
+// ---------------------------------------------------------------------------
+// WsCallback events
+// ---------------------------------------------------------------------------
+
+/// Text events: partial, silence, warning and error.
+#[test]
+fn callback_text_events() {
+    let sent = events(|cb| {
+        cb.on_partial("hel".to_string());
+        cb.on_silence();
+        cb.on_warning("overflow".to_string());
+        cb.on_error("boom".to_string());
+    });
+    assert_eq!(
+        sent,
+        vec![
+            json!({ "type": "partial", "text": "hel" }),
+            json!({ "type": "silence" }),
+            json!({ "type": "warning", "message": "overflow" }),
+            json!({ "type": "error", "message": "boom" }),
+        ]
+    );
+}
+
+/// Final segments carry their times and words.
+#[test]
+fn callback_final_segment() {
+    let segment = TranscriptSegment::from_words(vec![WordTimestamp {
+        text: "Hi.".to_string(),
+        start: 1.0,
+        end: 1.5,
+    }]);
+    let sent = events(|cb| cb.on_final_segment(segment));
+    assert_eq!(
+        sent,
+        vec![json!({
+            "type": "final_segment",
+            "segment": {
+                "text": "Hi.",
+                "start": 1.0,
+                "end": 1.5,
+                "words": [{ "text": "Hi.", "start": 1.0, "end": 1.5 }],
+            }
+        })]
+    );
+}
+
+/// Level events carry the meter values.
+#[test]
+fn callback_level() {
+    let sent = events(|cb| {
+        cb.on_level(InputLevel {
+            rms_dbfs: -20.0,
+            peak_dbfs: -6.0,
+            speech_probability: 0.5,
+        })
+    });
+    assert_eq!(
+        sent,
+        vec![json!({
+            "type": "level",
+            "rms_dbfs": -20.0,
+            "peak_dbfs": -6.0,
+            "speech_probability": 0.5,
+        })]
+    );
+}
+
+/// State events; done carries the text and the saved audio path (or null).
+#[test]
+fn callback_state_events() {
+    let sent = events(|cb| {
+        cb.on_state_change(RecordingState::Listening);
+        cb.on_state_change(RecordingState::Processing);
+        cb.on_state_change(RecordingState::Done {
+            text: "Hi.".to_string(),
+            audio_path: None,
+        });
+        cb.on_state_change(RecordingState::Error {
+            message: "no model".to_string(),
+        });
+    });
+    assert_eq!(
+        sent,
+        vec![
+            json!({ "type": "state", "state": "listening" }),
+            json!({ "type": "state", "state": "processing" }),
+            json!({ "type": "state", "state": "done", "text": "Hi.", "audio_path": null }),
+            json!({ "type": "state", "state": "error", "message": "no model" }),
+        ]
+    );
+}
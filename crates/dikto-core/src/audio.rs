@@ -1,10 +1,12 @@
+use crate::resample::{ResampleQuality, Resampler};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::Sample;
 use ringbuf::traits::{Consumer, Observer, Producer, Split};
 use ringbuf::{HeapCons, HeapProd, HeapRb};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 #[derive(Debug, Error)]
 pub enum AudioError {
@@ -18,6 +20,8 @@ pub enum AudioError {
     StreamPlay(String),
     #[error("Device error: {0}")]
     Device(String),
+    #[error("Audio source error: {0}")]
+    Source(String),
 }
 
 /// Configuration for audio capture.
@@ -27,6 +31,12 @@ pub struct AudioCaptureConfig {
     pub target_sample_rate: u32,
     /// Ring buffer capacity in samples
     pub buffer_capacity: usize,
+    /// Input device name, or None for the system default
+    pub device_name: Option<String>,
+    /// Quality of the conversion from the device rate to the target rate
+    pub resample_quality: ResampleQuality,
+    /// Device channels (1-based) averaged into the mono signal; empty uses all
+    pub input_channels: Vec<u16>,
 }
 
 impl Default for AudioCaptureConfig {
@@ -35,23 +45,173 @@ impl Default for AudioCaptureConfig {
             target_sample_rate: 16000,
             // 30 seconds at 16kHz
             buffer_capacity: 16000 * 30,
+            device_name: None,
+            resample_quality: ResampleQuality::default(),
+            input_channels: Vec::new(),
         }
     }
 }
 
+/// An available audio input device.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct InputDeviceInfo {
+    pub name: String,
+    pub default_sample_rate: u32,
+    pub channels: u16,
+    pub is_default: bool,
+}
+
+/// List the input devices of the default host.
+/// Devices whose name or default config cannot be queried are skipped.
+pub fn list_input_devices() -> Vec<InputDeviceInfo> {
+    let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+
+    let devices = match host.input_devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            warn!("Failed to enumerate input devices: {e}");
+            return Vec::new();
+        }
+    };
+
+    devices
+        .filter_map(|device| {
+            let name = device.name().ok()?;
+            let config = device.default_input_config().ok()?;
+            Some(InputDeviceInfo {
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                name,
+                default_sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+            })
+        })
+        .collect()
+}
+
+/// Find an input device by name, falling back to the system default
+/// (with a warning) when it is not connected.
+fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Option<cpal::Device> {
+    let devices = host
+        .input_devices()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| d.name().ok().map(|n| (n, d)));
+    find_device_or_default(devices, name, || host.default_input_device())
+}
+
+/// Pick the device called `name` from `(name, device)` pairs. Without a
+/// name, or if it is not among them (with a warning), use `default`.
+pub fn find_device_or_default<D>(
+    devices: impl IntoIterator<Item = (String, D)>,
+    name: Option<&str>,
+    default: impl FnOnce() -> Option<D>,
+) -> Option<D> {
+    if let Some(name) = name {
+        match devices.into_iter().find(|(n, _)| n == name) {
+            Some((_, device)) => return Some(device),
+            None => warn!("Input device '{name}' not found, using the default device"),
+        }
+    }
+    default()
+}
+
+/// Device frames processed per step of the input callback. Larger device
+/// buffers are split, so the scratch buffers never need to grow.
+const MAX_BLOCK_FRAMES: usize = 4096;
+
+/// The real-time part of capture, run inside the device callback: downmixes
+/// interleaved device samples to mono, resamples them and queues them in
+/// the ring buffer. It never allocates or locks; samples that don't fit in
+/// the ring buffer (because the reader fell behind) are counted as dropped.
+pub struct InputProcessor {
+    channels: usize,
+    /// Indices of the channels mixed into the mono signal.
+    selected: Vec<usize>,
+    resampler: Resampler,
+    mono: Vec<f32>,
+    resampled: Vec<f32>,
+    producer: HeapProd<f32>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl InputProcessor {
+    /// Set up the scratch buffers for `channels`-channel input, mixing the
+    /// `input_channels` (1-based; empty means all) into mono. Samples
+    /// dropped on overflow are added to `dropped`.
+    pub fn new(
+        channels: u16,
+        input_channels: &[u16],
+        mut resampler: Resampler,
+        producer: HeapProd<f32>,
+        dropped: Arc<AtomicU64>,
+    ) -> Self {
+        resampler.reserve(MAX_BLOCK_FRAMES);
+        Self {
+            channels: channels.max(1) as usize,
+            selected: select_channels(channels.max(1), input_channels),
+            mono: Vec::with_capacity(MAX_BLOCK_FRAMES),
+            resampled: Vec::with_capacity(resampler.max_output_len(MAX_BLOCK_FRAMES)),
+            resampler,
+            producer,
+            dropped,
+        }
+    }
+
+    /// Process one device buffer of interleaved samples.
+    pub fn process<T: Copy>(&mut self, data: &[T], to_f32: impl Fn(T) -> f32) {
+        let channels = self.channels;
+        let selected = &self.selected;
+        for block in data.chunks(MAX_BLOCK_FRAMES * channels) {
+            self.mono.clear();
+            self.mono.extend(block.chunks_exact(channels).map(|frame| {
+                let sum: f32 = selected.iter().map(|&c| to_f32(frame[c])).sum();
+                sum / selected.len() as f32
+            }));
+
+            // Band-limit and resample to the target rate
+            self.resampled.clear();
+            self.resampler.process(&self.mono, &mut self.resampled);
+            let pushed = self.producer.push_slice(&self.resampled);
+            if pushed < self.resampled.len() {
+                self.dropped
+                    .fetch_add((self.resampled.len() - pushed) as u64, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Indices of the 1-based `input_channels` that exist on a `channels`-channel
+/// device. Channels the device doesn't have are skipped with a warning;
+/// if none remain (or none were given), all channels are used.
+fn select_channels(channels: u16, input_channels: &[u16]) -> Vec<usize> {
+    let mut selected: Vec<usize> = Vec::new();
+    for &channel in input_channels {
+        if channel == 0 || channel > channels {
+            warn!("Input channel {channel} not available (device has {channels}), ignoring it");
+        } else if !selected.contains(&(channel as usize - 1)) {
+            selected.push(channel as usize - 1);
+        }
+    }
+    if selected.is_empty() {
+        selected = (0..channels as usize).collect();
+    }
+    selected
+}
+
 /// Handle to a running audio capture session.
 pub struct AudioCapture {
     _stream: cpal::Stream,
     consumer: HeapCons<f32>,
     running: Arc<AtomicBool>,
+    dropped: Arc<AtomicU64>,
 }
 
 impl AudioCapture {
-    /// Start capturing audio from the default input device.
+    /// Start capturing audio from the configured input device (or the default one).
     pub fn start(config: AudioCaptureConfig) -> Result<Self, AudioError> {
         let host = cpal::default_host();
-        let device = host
-            .default_input_device()
+        let device = select_input_device(&host, config.device_name.as_deref())
             .ok_or(AudioError::NoInputDevice)?;
 
         let device_name = device.name().unwrap_or_else(|_| "unknown".to_string());
@@ -74,17 +234,21 @@ impl AudioCapture {
         let running = Arc::new(AtomicBool::new(true));
         let running_clone = running.clone();
 
-        let target_rate = config.target_sample_rate;
-
-        let stream = build_stream(
-            &device,
-            &supported_config,
-            producer,
-            running_clone,
-            target_rate,
-            device_channels,
+        let resampler = Resampler::new(
             device_sample_rate,
-        )?;
+            config.target_sample_rate,
+            config.resample_quality,
+        );
+        let dropped = Arc::new(AtomicU64::new(0));
+        let processor = InputProcessor::new(
+            device_channels,
+            &config.input_channels,
+            resampler,
+            producer,
+            dropped.clone(),
+        );
+
+        let stream = build_stream(&device, &supported_config, processor, running_clone)?;
 
         stream
             .play()
@@ -96,6 +260,7 @@ impl AudioCapture {
             _stream: stream,
             consumer,
             running,
+            dropped,
         })
     }
 
@@ -112,6 +277,11 @@ impl AudioCapture {
         buf
     }
 
+    /// Samples lost so far because the ring buffer was full.
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
     /// Check if the capture is still running.
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::Relaxed)
@@ -130,25 +300,18 @@ impl Drop for AudioCapture {
     }
 }
 
-/// Build a cpal input stream that writes resampled mono samples into the ring buffer.
+/// Build a cpal input stream that feeds device buffers to `processor`.
 fn build_stream(
     device: &cpal::Device,
     config: &cpal::SupportedStreamConfig,
-    mut producer: HeapProd<f32>,
+    mut processor: InputProcessor,
     running: Arc<AtomicBool>,
-    target_rate: u32,
-    channels: u16,
-    device_rate: u32,
 ) -> Result<cpal::Stream, AudioError> {
     let sample_format = config.sample_format();
     let stream_config: cpal::StreamConfig = config.clone().into();
 
-    // Resampling state: we use linear interpolation for downsampling
-    let ratio = device_rate as f64 / target_rate as f64;
-    let mut resample_pos: f64 = 0.0;
-
     macro_rules! build_input_stream {
-        ($sample_type:ty, $to_f32:expr) => {{
+        ($sample_type:ty) => {{
             device
                 .build_input_stream(
                     &stream_config,
@@ -156,36 +319,7 @@ fn build_stream(
                         if !running.load(Ordering::Relaxed) {
                             return;
                         }
-
-                        // Convert to mono f32
-                        let mono: Vec<f32> = data
-                            .chunks(channels as usize)
-                            .map(|frame| {
-                                let sum: f32 = frame.iter().map(|s| $to_f32(*s)).sum();
-                                sum / channels as f32
-                            })
-                            .collect();
-
-                        // Resample to target rate using linear interpolation
-                        if device_rate == target_rate {
-                            // No resampling needed
-                            let _ = producer.push_slice(&mono);
-                        } else {
-                            let mut resampled = Vec::new();
-                            while (resample_pos as usize) < mono.len().saturating_sub(1) {
-                                let idx = resample_pos as usize;
-                                let frac = resample_pos - idx as f64;
-                                let sample =
-                                    mono[idx] * (1.0 - frac as f32) + mono[idx + 1] * frac as f32;
-                                resampled.push(sample);
-                                resample_pos += ratio;
-                            }
-                            resample_pos -= mono.len() as f64;
-                            if resample_pos < 0.0 {
-                                resample_pos = 0.0;
-                            }
-                            let _ = producer.push_slice(&resampled);
-                        }
+                        processor.process(data, f32::from_sample::<$sample_type>);
                     },
                     move |err| {
                         error!("Audio input error: {err}");
@@ -196,14 +330,19 @@ fn build_stream(
         }};
     }
 
+    // Integer formats are scaled to -1.0..1.0, unsigned ones around their midpoint
     let stream = match sample_format {
-        cpal::SampleFormat::F32 => build_input_stream!(f32, |s: f32| s),
-        cpal::SampleFormat::I16 => {
-            build_input_stream!(i16, |s: i16| s as f32 / i16::MAX as f32)
-        }
-        cpal::SampleFormat::U16 => {
-            build_input_stream!(u16, |s: u16| (s as f32 / u16::MAX as f32) * 2.0 - 1.0)
-        }
+        cpal::SampleFormat::F32 => build_input_stream!(f32),
+        cpal::SampleFormat::F64 => build_input_stream!(f64),
+        cpal::SampleFormat::I8 => build_input_stream!(i8),
+        cpal::SampleFormat::I16 => build_input_stream!(i16),
+        cpal::SampleFormat::I32 => build_input_stream!(i32),
+        cpal::SampleFormat::I64 => build_input_stream!(i64),
+        cpal::SampleFormat::U8 => build_input_stream!(u8),
+        cpal::SampleFormat::U16 => build_input_stream!(u16),
+        cpal::SampleFormat::U32 => build_input_stream!(u32),
+        cpal::SampleFormat::U64 => build_input_stream!(u64),
+        // SampleFormat is non-exhaustive; formats added by newer cpal versions
         _ => {
             return Err(AudioError::StreamBuild(format!(
                 "Unsupported sample format: {sample_format:?}"
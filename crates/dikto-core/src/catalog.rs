@@ -0,0 +1,241 @@
+use crate::config::{self, catalog_path};
+use crate::models::{
+    self, cached_model_list, parse_model_list, DownloadOptions, ModelError, ModelInfo, ModelList,
+    ModelListCache,
+};
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+// The remote catalog is a model list in the manifest format, published at a
+// configurable URL next to `<url>.sha256` (the hex SHA-256 of the JSON, as
+// written by `sha256sum`). Entries with a built-in name describe a newer
+// version of that model; other entries add models. The last fetched catalog
+// is cached in the data dir, so the catalog works offline.
+//
+// The checksum comes from the same server, so it only catches corrupted
+// transfers. Trust comes from `catalog_public_key` in the config: with a key
+// set, the catalog must also be signed (`<url>.sig`, a base64 ed25519
+// signature of the JSON). The signature is cached next to the catalog and
+// checked on every load. Only a signed catalog may replace built-in models;
+// otherwise whoever serves the catalog could point them at other files.
+
+/// Parse and validate a catalog: the manifest rules, except that built-in
+/// names are allowed (as updates) and every file needs a SHA-256.
+pub fn parse_catalog(json: &str) -> Result<Vec<ModelInfo>, ModelError> {
+    parse_model_list(json, ModelList::Catalog).map_err(ModelError::InvalidCatalog)
+}
+
+/// Path of the cached signature of the catalog at `path`.
+pub fn signature_path(path: &Path) -> PathBuf {
+    path.with_extension("json.sig")
+}
+
+/// Check a base64 ed25519 `signature` of `json` against the base64
+/// `public_key`. Whitespace in both is ignored (`base64` wraps its output).
+pub fn verify_signature(json: &[u8], signature: &str, public_key: &str) -> Result<(), ModelError> {
+    let decode = |what: &str, s: &str| {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        base64::engine::general_purpose::STANDARD
+            .decode(s)
+            .map_err(|e| ModelError::InvalidCatalog(format!("invalid {what}: {e}")))
+    };
+    let key_bytes: [u8; 32] = decode("catalog_public_key", public_key)?
+        .try_into()
+        .map_err(|_| {
+            ModelError::InvalidCatalog("invalid catalog_public_key: not 32 bytes".to_string())
+        })?;
+    let key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| ModelError::InvalidCatalog(format!("invalid catalog_public_key: {e}")))?;
+    let signature = Signature::from_slice(&decode("signature", signature)?)
+        .map_err(|e| ModelError::InvalidCatalog(format!("invalid signature: {e}")))?;
+    key.verify_strict(json, &signature)
+        .map_err(|_| ModelError::InvalidCatalog("signature mismatch".to_string()))
+}
+
+/// Drop the entries of an unsigned catalog that would replace built-in models.
+pub fn without_builtin_updates(catalog: Vec<ModelInfo>) -> Vec<ModelInfo> {
+    catalog
+        .into_iter()
+        .filter(|m| {
+            let builtin = models::MODELS.iter().any(|b| b.name == m.name);
+            if builtin {
+                warn!(
+                    "Ignoring catalog entry for built-in model '{}': the catalog is not signed",
+                    m.name
+                );
+            }
+            !builtin
+        })
+        .collect()
+}
+
+/// Read and validate the cached catalog at `path`. A missing file means no catalog.
+pub fn load_catalog(path: &Path) -> Result<Vec<ModelInfo>, ModelError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => parse_catalog(&json),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(ModelError::Io(e)),
+    }
+}
+
+/// Like `load_catalog`, applying trust: with a `public_key`, the cached
+/// signature must match (or the whole catalog is rejected); without one,
+/// entries for built-in models are dropped.
+pub fn load_trusted_catalog(
+    path: &Path,
+    public_key: Option<&str>,
+) -> Result<Vec<ModelInfo>, ModelError> {
+    let catalog = load_catalog(path)?;
+    let Some(public_key) = public_key else {
+        return Ok(without_builtin_updates(catalog));
+    };
+    if catalog.is_empty() {
+        return Ok(catalog);
+    }
+    let json = std::fs::read(path)?;
+    let signature = std::fs::read_to_string(signature_path(path))
+        .map_err(|e| ModelError::InvalidCatalog(format!("no signature: {e}")))?;
+    verify_signature(&json, &signature, public_key)?;
+    Ok(catalog)
+}
+
+fn load_configured_catalog(path: &Path) -> Result<Vec<ModelInfo>, ModelError> {
+    load_trusted_catalog(path, config::load_config().catalog_public_key.as_deref())
+}
+
+static CATALOG_CACHE: Mutex<Option<ModelListCache>> = Mutex::new(None);
+
+/// Models from the cached catalog, trusted as configured. Trust is checked
+/// again whenever the catalog, its signature or the config changes. An
+/// invalid cache is logged and ignored.
+pub fn catalog_models() -> &'static [ModelInfo] {
+    let (Ok(path), Ok(config_path)) = (catalog_path(), config::config_path()) else {
+        return &[];
+    };
+    cached_model_list(
+        &CATALOG_CACHE,
+        &path,
+        &[&signature_path(&path), &config_path],
+        load_configured_catalog,
+    )
+}
+
+/// A fetched catalog: its JSON and, if a public key was given, the verified
+/// signature.
+#[derive(Debug, Clone)]
+pub struct FetchedCatalog {
+    pub json: String,
+    pub signature: Option<String>,
+}
+
+/// Fetch the catalog at `url`, check it against `<url>.sha256`, with a
+/// `public_key` also against the signature at `<url>.sig`, and validate it.
+pub async fn fetch_catalog(
+    url: &str,
+    public_key: Option<&str>,
+    options: &DownloadOptions,
+) -> Result<FetchedCatalog, ModelError> {
+    let client = options.build_client()?;
+    let get = |url: String| {
+        let client = client.clone();
+        async move {
+            let response = client.get(&url).send().await?;
+            if !response.status().is_success() {
+                return Err(ModelError::HttpStatus(response.status().as_u16(), url));
+            }
+            Ok(response.bytes().await?)
+        }
+    };
+
+    let body = get(url.to_string()).await?;
+    let checksum = get(format!("{url}.sha256")).await?;
+    let expected = String::from_utf8_lossy(&checksum)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual = format!("{:x}", Sha256::digest(&body));
+    if expected != actual {
+        return Err(ModelError::InvalidCatalog(format!(
+            "checksum mismatch (expected '{expected}', got {actual})"
+        )));
+    }
+
+    let signature = match public_key {
+        Some(public_key) => {
+            let signature = get(format!("{url}.sig")).await?;
+            let signature = String::from_utf8_lossy(&signature).into_owned();
+            verify_signature(&body, &signature, public_key)?;
+            Some(signature)
+        }
+        None => None,
+    };
+
+    let json = String::from_utf8(body.to_vec())
+        .map_err(|e| ModelError::InvalidCatalog(format!("not UTF-8: {e}")))?;
+    parse_catalog(&json)?;
+    Ok(FetchedCatalog { json, signature })
+}
+
+/// Fetch the catalog at `url` and store it as the cached catalog. Returns
+/// its models, as trusted with `public_key`.
+pub async fn refresh_catalog(
+    url: &str,
+    public_key: Option<&str>,
+    options: &DownloadOptions,
+) -> Result<Vec<ModelInfo>, ModelError> {
+    let path = catalog_path().map_err(|e| ModelError::InvalidCatalog(e.to_string()))?;
+    refresh_catalog_to(url, &path, public_key, options).await
+}
+
+/// Like `refresh_catalog`, storing the catalog at `path` and its signature
+/// next to it. Files are replaced atomically, so readers never see a partial
+/// catalog; the signature goes first, as the catalog's mtime invalidates the
+/// loaded catalog.
+pub async fn refresh_catalog_to(
+    url: &str,
+    path: &Path,
+    public_key: Option<&str>,
+    options: &DownloadOptions,
+) -> Result<Vec<ModelInfo>, ModelError> {
+    let fetched = fetch_catalog(url, public_key, options).await?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let signature_path = signature_path(path);
+    match &fetched.signature {
+        Some(signature) => {
+            let temp = path.with_extension("json.sig.downloading");
+            std::fs::write(&temp, signature)?;
+            std::fs::rename(&temp, &signature_path)?;
+        }
+        None => {
+            if signature_path.exists() {
+                std::fs::remove_file(&signature_path)?;
+            }
+        }
+    }
+    let temp = path.with_extension("json.downloading");
+    std::fs::write(&temp, &fetched.json)?;
+    std::fs::rename(&temp, path)?;
+
+    let catalog = parse_catalog(&fetched.json)?;
+    let catalog = if fetched.signature.is_some() {
+        catalog
+    } else {
+        without_builtin_updates(catalog)
+    };
+    let added = catalog
+        .iter()
+        .filter(|m| !models::MODELS.iter().any(|b| b.name == m.name))
+        .count();
+    info!(
+        "Catalog from {url}: {} model(s), {added} not built in",
+        catalog.len()
+    );
+    Ok(catalog)
+}
@@ -0,0 +1,168 @@
+use crate::transcribe::TranscriptSegment;
+
+/// Sample rate of all audio handled by the chunker.
+const SAMPLE_RATE: f64 = 16000.0;
+
+/// How long audio is split into model-sized chunks.
+#[derive(Debug, Clone)]
+pub struct ChunkConfig {
+    /// Longest chunk handed to the model, in samples.
+    pub max_chunk_samples: usize,
+    /// Audio shared by consecutive chunks, in samples, so words at a cut are not lost.
+    pub overlap_samples: usize,
+    /// VAD frames with a speech probability below this count as silence.
+    pub silence_threshold: f32,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            // 60 seconds at 16kHz
+            max_chunk_samples: 60 * 16000,
+            // 1 second at 16kHz
+            overlap_samples: 16000,
+            silence_threshold: 0.3,
+        }
+    }
+}
+
+/// A chunk of a longer buffer, as a sample range `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioChunk {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Plan how to split `total_samples` of audio into chunks of at most
+/// `max_chunk_samples`. Each cut is placed in the middle of the longest
+/// silence in the second half of the chunk, using per-frame VAD speech
+/// probabilities (`frame_samples` each). Without silence, the chunk is cut at
+/// its maximum length. Consecutive chunks overlap by `overlap_samples`.
+/// The chunks always cover the whole buffer.
+pub fn plan_chunks(
+    total_samples: usize,
+    frame_probs: &[f32],
+    frame_samples: usize,
+    config: &ChunkConfig,
+) -> Vec<AudioChunk> {
+    let max = config.max_chunk_samples.max(1);
+    // Overlap must stay well below the search window so every chunk makes progress
+    let overlap = config.overlap_samples.min(max / 4);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < total_samples {
+        let hard_end = start + max;
+        if hard_end >= total_samples {
+            chunks.push(AudioChunk {
+                start,
+                end: total_samples,
+            });
+            break;
+        }
+
+        let cut = find_silence_cut(
+            frame_probs,
+            frame_samples,
+            start + max / 2,
+            hard_end,
+            config.silence_threshold,
+        )
+        .unwrap_or(hard_end);
+        chunks.push(AudioChunk { start, end: cut });
+        start = cut - overlap;
+    }
+    chunks
+}
+
+/// Middle of the longest run of silent frames fully inside `from..to`
+/// (later runs win ties), or None if there is no silent frame.
+fn find_silence_cut(
+    frame_probs: &[f32],
+    frame_samples: usize,
+    from: usize,
+    to: usize,
+    threshold: f32,
+) -> Option<usize> {
+    if frame_samples == 0 {
+        return None;
+    }
+    let first = from.div_ceil(frame_samples);
+    let last = (to / frame_samples).min(frame_probs.len());
+    if first >= last {
+        return None;
+    }
+
+    let mut best: Option<(usize, usize)> = None; // (run start frame, run length)
+    let mut run_start = first;
+    // A trailing non-silent sentinel closes the final run
+    let silent_frames = frame_probs[first..last]
+        .iter()
+        .map(|&p| p < threshold)
+        .chain(std::iter::once(false));
+    for (offset, silent) in silent_frames.enumerate() {
+        if silent {
+            continue;
+        }
+        let frame = first + offset;
+        let len = frame - run_start;
+        if len > 0 && !matches!(best, Some((_, best_len)) if len < best_len) {
+            best = Some((run_start, len));
+        }
+        run_start = frame + 1;
+    }
+
+    best.map(|(run_start, len)| (run_start * frame_samples + len * frame_samples / 2).min(to))
+}
+
+/// Combine per-chunk transcription results into one timeline.
+/// Segment and word times are shifted by each chunk's start. In the overlap
+/// between two chunks, words are taken from the earlier chunk up to the middle
+/// of the overlap and from the later chunk after it, so nothing is duplicated.
+pub fn stitch_segments(
+    chunks: &[AudioChunk],
+    results: Vec<Vec<TranscriptSegment>>,
+) -> Vec<TranscriptSegment> {
+    let mut stitched = Vec::new();
+    for (i, (chunk, segments)) in chunks.iter().zip(results).enumerate() {
+        let lo = match i {
+            0 => f64::NEG_INFINITY,
+            _ => (chunk.start + chunks[i - 1].end) as f64 / 2.0 / SAMPLE_RATE,
+        };
+        let hi = match chunks.get(i + 1) {
+            Some(next) => (next.start + chunk.end) as f64 / 2.0 / SAMPLE_RATE,
+            None => f64::INFINITY,
+        };
+        let keep = |start: f64, end: f64| {
+            let mid = (start + end) / 2.0;
+            mid >= lo && mid < hi
+        };
+
+        for mut segment in segments {
+            segment.offset(chunk.start as f64 / SAMPLE_RATE);
+            if segment.words.is_empty() {
+                if keep(segment.start, segment.end) {
+                    stitched.push(segment);
+                }
+                continue;
+            }
+
+            let total_words = segment.words.len();
+            let words: Vec<_> = segment
+                .words
+                .drain(..)
+                .filter(|w| keep(w.start, w.end))
+                .collect();
+            if words.len() == total_words {
+                segment.words = words;
+                stitched.push(segment);
+            } else if !words.is_empty() {
+                stitched.push(TranscriptSegment {
+                    is_final: segment.is_final,
+                    ..TranscriptSegment::from_words(words)
+                });
+            }
+        }
+    }
+    stitched
+}
@@ -1,3 +1,4 @@
+use crate::resample::ResampleQuality;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tracing::warn;
@@ -22,6 +23,9 @@ pub enum ActivationMode {
     Hold,
 }
 
+/// Upper bound for `max_duration` (1 hour). Long recordings are transcribed in chunks.
+pub const MAX_DURATION_SECS: u32 = 3600;
+
 /// Valid modifier names for shortcut strings.
 const VALID_MODIFIERS: &[&str] = &["option", "command", "control", "shift"];
 
@@ -61,6 +65,54 @@ pub struct DiktoConfig {
     pub auto_copy: bool,
     #[serde(default)]
     pub activation_mode: ActivationMode,
+    /// Input device name; None uses the system default.
+    #[serde(default)]
+    pub input_device: Option<String>,
+    /// Input channels (1-based) averaged into the mono signal, e.g. `[3]` for
+    /// the mic on channel 3 of an audio interface. Empty averages all channels.
+    #[serde(default)]
+    pub input_channels: Vec<u16>,
+    /// Quality of the resampler converting the microphone to 16kHz.
+    #[serde(default)]
+    pub resample_quality: ResampleQuality,
+    /// Keep partial files of cancelled downloads so they resume next time.
+    #[serde(default = "default_true")]
+    pub keep_partial_downloads: bool,
+    /// Retries per file after network errors, timeouts or server errors.
+    #[serde(default = "default_download_retries")]
+    pub download_retries: u32,
+    /// Seconds allowed for connecting to the download server.
+    #[serde(default = "default_download_connect_timeout_secs")]
+    pub download_connect_timeout_secs: u32,
+    /// Seconds a download may stall before it fails (and is retried).
+    #[serde(default = "default_download_read_timeout_secs")]
+    pub download_read_timeout_secs: u32,
+    /// Proxy URL for model downloads. None uses the `HTTPS_PROXY`/`ALL_PROXY` env vars.
+    #[serde(default)]
+    pub download_proxy: Option<String>,
+    /// Base URL replacing the host of model download URLs (e.g. a Hugging Face
+    /// mirror). None uses `HF_ENDPOINT` if set.
+    #[serde(default)]
+    pub download_mirror: Option<String>,
+    /// How many files of a multi-file model are downloaded at once.
+    #[serde(default = "default_download_parallel_files")]
+    pub download_parallel_files: u32,
+    /// URL of the remote model catalog. None disables the catalog.
+    #[serde(default)]
+    pub catalog_url: Option<String>,
+    /// Base64 ed25519 public key the catalog must be signed with. Only a
+    /// signed catalog may update built-in models.
+    #[serde(default)]
+    pub catalog_public_key: Option<String>,
+    /// Save the audio of each session as a WAV file in the recordings dir.
+    #[serde(default)]
+    pub save_audio: bool,
+    /// Most session recordings kept; older ones are deleted. 0 keeps all.
+    #[serde(default = "default_save_audio_max_files")]
+    pub save_audio_max_files: u32,
+    /// Days a session recording is kept. 0 keeps them regardless of age.
+    #[serde(default = "default_save_audio_max_age_days")]
+    pub save_audio_max_age_days: u32,
 }
 
 pub fn default_model_name() -> String {
@@ -87,6 +139,30 @@ fn default_true() -> bool {
     true
 }
 
+fn default_download_retries() -> u32 {
+    3
+}
+
+fn default_download_connect_timeout_secs() -> u32 {
+    15
+}
+
+fn default_download_read_timeout_secs() -> u32 {
+    60
+}
+
+fn default_download_parallel_files() -> u32 {
+    3
+}
+
+fn default_save_audio_max_files() -> u32 {
+    50
+}
+
+fn default_save_audio_max_age_days() -> u32 {
+    7
+}
+
 fn default_global_shortcut() -> Option<String> {
     Some("option+space".to_string())
 }
@@ -103,6 +179,21 @@ impl Default for DiktoConfig {
             auto_paste: true,
             auto_copy: true,
             activation_mode: ActivationMode::Hold,
+            input_device: None,
+            input_channels: Vec::new(),
+            resample_quality: ResampleQuality::default(),
+            keep_partial_downloads: true,
+            download_retries: default_download_retries(),
+            download_connect_timeout_secs: default_download_connect_timeout_secs(),
+            download_read_timeout_secs: default_download_read_timeout_secs(),
+            download_proxy: None,
+            download_mirror: None,
+            download_parallel_files: default_download_parallel_files(),
+            catalog_url: None,
+            catalog_public_key: None,
+            save_audio: false,
+            save_audio_max_files: default_save_audio_max_files(),
+            save_audio_max_age_days: default_save_audio_max_age_days(),
         }
     }
 }
@@ -110,9 +201,34 @@ impl Default for DiktoConfig {
 impl DiktoConfig {
     /// Clamp all numeric fields to safe ranges and validate shortcut and language.
     pub fn validate(&mut self) {
-        self.max_duration = self.max_duration.clamp(1, 120);
+        self.max_duration = self.max_duration.clamp(1, MAX_DURATION_SECS);
         self.silence_duration_ms = self.silence_duration_ms.clamp(250, 10000);
         self.speech_threshold = self.speech_threshold.clamp(0.01, 0.99);
+        self.download_retries = self.download_retries.min(10);
+        self.download_connect_timeout_secs = self.download_connect_timeout_secs.clamp(1, 300);
+        self.download_read_timeout_secs = self.download_read_timeout_secs.clamp(1, 600);
+        self.download_parallel_files = self.download_parallel_files.clamp(1, 8);
+
+        // Channels are 1-based; drop zeros and duplicates
+        let mut channels: Vec<u16> = Vec::new();
+        for &channel in &self.input_channels {
+            if channel > 0 && !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        }
+        self.input_channels = channels;
+
+        // Empty strings from the settings UI mean "not set"
+        for value in [
+            &mut self.download_proxy,
+            &mut self.download_mirror,
+            &mut self.catalog_url,
+            &mut self.catalog_public_key,
+        ] {
+            if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+                *value = None;
+            }
+        }
 
         // Validate language code: must be 2-4 lowercase letters or "auto"
         if self.language != "auto" {
@@ -167,11 +283,26 @@ pub fn models_dir() -> PathBuf {
     }
 }
 
+/// Returns the session recordings directory path: ~/.local/share/dikto/recordings/
+pub fn recordings_dir() -> Result<PathBuf, ConfigError> {
+    Ok(data_dir()?.join("recordings"))
+}
+
 /// Returns the config file path: ~/.config/dikto/config.json
 pub fn config_path() -> Result<PathBuf, ConfigError> {
     Ok(config_dir()?.join("config.json"))
 }
 
+/// Returns the user model manifest path: ~/.config/dikto/models.json
+pub fn models_manifest_path() -> Result<PathBuf, ConfigError> {
+    Ok(config_dir()?.join("models.json"))
+}
+
+/// Returns the cached remote model catalog path: ~/.local/share/dikto/catalog.json
+pub fn catalog_path() -> Result<PathBuf, ConfigError> {
+    Ok(data_dir()?.join("catalog.json"))
+}
+
 /// Load config from disk, with env var overrides for backward compatibility.
 /// Migration: existing config files without `activation_mode` get Toggle (preserves behavior).
 /// New installs get Hold (push-to-talk).
@@ -0,0 +1,150 @@
+use crate::resample::{self, ResampleQuality};
+use std::fs::File;
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Sample rate expected by all ASR backends.
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
+
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("Failed to open audio file: {0}")]
+    Open(#[from] std::io::Error),
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+    #[error("No audio track found")]
+    NoAudioTrack,
+    #[error("Decoding failed: {0}")]
+    Decode(String),
+}
+
+/// Decode an audio file (WAV, FLAC, MP3, OGG/Vorbis) into 16kHz mono f32 samples.
+pub fn decode_file(path: &Path) -> Result<Vec<f32>, DecodeError> {
+    let file = File::open(path)?;
+    let extension = path.extension().and_then(|e| e.to_str());
+    decode_source(Box::new(file), extension)
+}
+
+/// Decode an in-memory audio file (e.g. an upload) into 16kHz mono f32 samples.
+/// `extension` is an optional format hint such as "mp3".
+pub fn decode_bytes(bytes: Vec<u8>, extension: Option<&str>) -> Result<Vec<f32>, DecodeError> {
+    decode_source(Box::new(std::io::Cursor::new(bytes)), extension)
+}
+
+fn decode_source(
+    source: Box<dyn MediaSource>,
+    extension: Option<&str>,
+) -> Result<Vec<f32>, DecodeError> {
+    let mss = MediaSourceStream::new(source, Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = extension {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| DecodeError::UnsupportedFormat(e.to_string()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or(DecodeError::NoAudioTrack)?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| DecodeError::UnsupportedFormat(e.to_string()))?;
+
+    let mut mono: Vec<f32> = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(DecodeError::Decode(e.to_string())),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(buf) => {
+                let spec = *buf.spec();
+                sample_rate = spec.rate;
+                let mut samples = SampleBuffer::<f32>::new(buf.capacity() as u64, spec);
+                samples.copy_interleaved_ref(buf);
+                mono.extend(downmix(samples.samples(), spec.channels.count()));
+            }
+            // Corrupt packets are skipped rather than failing the whole file
+            Err(SymphoniaError::DecodeError(e)) => {
+                warn!("Skipping undecodable packet: {e}");
+            }
+            Err(e) => return Err(DecodeError::Decode(e.to_string())),
+        }
+    }
+
+    if sample_rate == 0 {
+        return Err(DecodeError::Decode("Unknown sample rate".to_string()));
+    }
+
+    debug!("Decoded {} samples at {}Hz", mono.len(), sample_rate);
+
+    // Files aren't real-time, so they always get the best filter
+    Ok(resample::resample(
+        &mono,
+        sample_rate,
+        TARGET_SAMPLE_RATE,
+        ResampleQuality::Best,
+    ))
+}
+
+/// Average interleaved multi-channel samples down to mono.
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Resample mono audio using linear interpolation. Cheap, but without an
+/// anti-aliasing filter; `resample::resample` is preferred for speech.
+pub fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio).floor() as usize;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let next = samples.get(idx + 1).copied().unwrap_or(samples[idx]);
+            samples[idx] * (1.0 - frac) + next * frac
+        })
+        .collect()
+}
@@ -1,12 +1,15 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use tracing::{debug, info, warn};
 
+use crate::chunk::{self, ChunkConfig};
 use crate::models::ModelBackend;
 use crate::transcribe::{
     ParakeetEngine, TranscribeConfig, TranscribeError, TranscriptSegment, WhisperEngine,
 };
+use crate::vad::{self, VAD_FRAME_SAMPLES};
 
 /// Unified ASR engine wrapping both Parakeet and Whisper backends.
 pub enum AsrEngine {
@@ -33,10 +36,7 @@ impl AsrEngine {
 
     /// Create a new transcription session.
     pub fn create_session(&self, config: TranscribeConfig) -> AsrSession {
-        AsrSession {
-            audio_buffer: Vec::new(),
-            language: config.language,
-        }
+        AsrSession::new(config.language)
     }
 }
 
@@ -46,10 +46,62 @@ pub struct LoadedEngine {
     pub engine: AsrEngine,
 }
 
+/// Minimum interval between partial decodes.
+const PARTIAL_MIN_INTERVAL: Duration = Duration::from_millis(700);
+/// Partial decoding waits this many times its last inference time before running
+/// again, so it uses at most 1/(1+N) of wall time and never falls behind real time.
+const PARTIAL_LOAD_FACTOR: u32 = 2;
+/// Minimum audio before the first partial decode (0.5s at 16kHz).
+const PARTIAL_MIN_SAMPLES: usize = 8000;
+/// Partials only decode the most recent audio (20s at 16kHz) to bound their cost.
+const PARTIAL_WINDOW_SAMPLES: usize = 20 * 16000;
+
+/// Schedules partial decodes so they adapt to how fast the model runs.
+#[derive(Debug, Clone)]
+pub struct PartialThrottle {
+    min_interval: Duration,
+    next_due: Option<Instant>,
+}
+
+impl PartialThrottle {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next_due: None,
+        }
+    }
+
+    /// True if a partial decode may run at `now`.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_due.is_none_or(|due| now >= due)
+    }
+
+    /// Record a decode that finished at `finished_at` after running for `inference`.
+    pub fn record(&mut self, finished_at: Instant, inference: Duration) {
+        let wait = self.min_interval.max(inference * PARTIAL_LOAD_FACTOR);
+        self.next_due = Some(finished_at + wait);
+    }
+}
+
+impl Default for PartialThrottle {
+    fn default() -> Self {
+        Self::new(PARTIAL_MIN_INTERVAL)
+    }
+}
+
 /// Unified transcription session that accumulates audio for batch inference.
 pub struct AsrSession {
     audio_buffer: Vec<f32>,
     language: String,
+    partial_throttle: PartialThrottle,
+    /// Buffer length at the last partial decode, to skip decodes with no new audio.
+    partial_decoded_len: usize,
+    /// How buffers longer than one model pass are split.
+    chunk_config: ChunkConfig,
+    /// Copy of every buffer passed to inference by `flush`, if enabled.
+    flushed_audio: Option<Vec<f32>>,
+    /// Most samples kept in `flushed_audio`; later audio is not kept.
+    flushed_audio_limit: usize,
 }
 
 impl AsrSession {
@@ -58,9 +110,33 @@ impl AsrSession {
         Self {
             audio_buffer: Vec::new(),
             language,
+            partial_throttle: PartialThrottle::default(),
+            partial_decoded_len: 0,
+            chunk_config: ChunkConfig::default(),
+            flushed_audio: None,
+            flushed_audio_limit: 0,
         }
     }
 
+    /// Override how long buffers are chunked (mainly for tests and tuning).
+    pub fn set_chunk_config(&mut self, config: ChunkConfig) {
+        self.chunk_config = config;
+    }
+
+    /// Keep a copy of the audio of every `flush`, for `take_flushed_audio`,
+    /// up to `max_samples`; audio flushed after that is not kept. The copy
+    /// lives in memory for the whole session, continuous sessions included.
+    pub fn keep_flushed_audio(&mut self, max_samples: usize) {
+        self.flushed_audio.get_or_insert_with(Vec::new);
+        self.flushed_audio_limit = max_samples;
+    }
+
+    /// The audio passed to inference by all flushes so far, concatenated.
+    /// None unless `keep_flushed_audio` was called.
+    pub fn take_flushed_audio(&mut self) -> Option<Vec<f32>> {
+        self.flushed_audio.take()
+    }
+
     /// Feed audio samples (16kHz mono f32).
     pub fn feed_samples(&mut self, samples: &[f32]) -> Vec<TranscriptSegment> {
         self.audio_buffer.extend_from_slice(samples);
@@ -83,45 +159,86 @@ impl AsrSession {
             self.audio_buffer.len()
         );
 
-        // Cap at ~4 minutes
-        const MAX_SAMPLES: usize = 4 * 60 * 16000;
-        if self.audio_buffer.len() > MAX_SAMPLES {
-            info!(
-                "Truncating audio from {:.1}s to 240s",
-                self.audio_buffer.len() as f32 / 16000.0
-            );
-            self.audio_buffer.truncate(MAX_SAMPLES);
+        if let Some(flushed) = &mut self.flushed_audio {
+            let room = self.flushed_audio_limit.saturating_sub(flushed.len());
+            if room > 0 && room < self.audio_buffer.len() {
+                warn!(
+                    "Kept session audio reached its limit of {:.0}s; later audio is not kept",
+                    self.flushed_audio_limit as f64 / 16000.0
+                );
+            }
+            let kept = room.min(self.audio_buffer.len());
+            flushed.extend_from_slice(&self.audio_buffer[..kept]);
         }
 
-        debug!("flush: acquiring engine lock...");
-        let mut guard = engine
-            .lock()
-            .map_err(|e| TranscribeError::Inference(format!("Lock poisoned: {e}")))?;
-
-        let loaded = guard.as_mut().ok_or(TranscribeError::NotLoaded)?;
-
-        debug!("flush: lock acquired, running inference...");
-
-        let start = std::time::Instant::now();
-        let text = match &mut loaded.engine {
-            AsrEngine::Parakeet(e) => e.transcribe(&self.audio_buffer)?,
-            AsrEngine::Whisper(e) => e.transcribe(&self.audio_buffer, &self.language)?,
+        let start = Instant::now();
+        let segments = if self.audio_buffer.len() > self.chunk_config.max_chunk_samples {
+            transcribe_long(
+                engine,
+                &self.audio_buffer,
+                &self.language,
+                &self.chunk_config,
+            )?
+        } else {
+            run_inference(engine, &self.audio_buffer, &self.language)?
         };
         debug!(
             "flush: inference done in {:.1}s",
             start.elapsed().as_secs_f32()
         );
         self.audio_buffer.clear();
+        self.partial_decoded_len = 0;
 
-        let text = text.trim().to_string();
-        if text.is_empty() || is_hallucination(&text) {
-            return Ok(Vec::new());
+        Ok(segments)
+    }
+
+    /// Re-decode the most recent audio for a live, non-final transcript.
+    /// Returns None when no decode is due yet (throttled), there is too little
+    /// new audio, or the result is empty. Does not consume the buffer.
+    pub fn partial(
+        &mut self,
+        engine: &Arc<Mutex<Option<LoadedEngine>>>,
+    ) -> Result<Option<TranscriptSegment>, TranscribeError> {
+        let len = self.audio_buffer.len();
+        if len < PARTIAL_MIN_SAMPLES
+            || len == self.partial_decoded_len
+            || !self.partial_throttle.is_due(Instant::now())
+        {
+            return Ok(None);
+        }
+
+        let window = &self.audio_buffer[len.saturating_sub(PARTIAL_WINDOW_SAMPLES)..];
+        let start = Instant::now();
+        let result = run_inference(engine, window, &self.language);
+        let inference = start.elapsed();
+        // Throttle failed decodes too, so errors are not retried in a tight loop
+        self.partial_throttle.record(Instant::now(), inference);
+        self.partial_decoded_len = len;
+        let segments = result?;
+        debug!(
+            "partial: {:.1}s of audio decoded in {:.2}s",
+            window.len() as f32 / 16000.0,
+            inference.as_secs_f32()
+        );
+
+        if segments.is_empty() {
+            return Ok(None);
         }
 
-        Ok(vec![TranscriptSegment {
-            text,
-            is_final: true,
-        }])
+        // Merge into one live segment, timed relative to the session buffer
+        let mut partial = TranscriptSegment {
+            text: segments
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            is_final: false,
+            start: segments[0].start,
+            end: segments[segments.len() - 1].end,
+            words: segments.into_iter().flat_map(|s| s.words).collect(),
+        };
+        partial.offset((len - window.len()) as f64 / 16000.0);
+        Ok(Some(partial))
     }
 
     /// Get accumulated audio buffer length in seconds.
@@ -130,6 +247,61 @@ impl AsrSession {
     }
 }
 
+/// Transcribe a buffer longer than one chunk: split it at VAD silences with
+/// overlap, transcribe each chunk, and stitch the results back together.
+/// If VAD is unavailable the audio is still fully transcribed, with cuts at
+/// the maximum chunk length.
+fn transcribe_long(
+    engine: &Arc<Mutex<Option<LoadedEngine>>>,
+    samples: &[f32],
+    language: &str,
+    config: &ChunkConfig,
+) -> Result<Vec<TranscriptSegment>, TranscribeError> {
+    let frame_probs = vad::speech_probabilities(samples).unwrap_or_else(|e| {
+        warn!("VAD failed ({e}), chunking long audio without silence detection");
+        Vec::new()
+    });
+    let chunks = chunk::plan_chunks(samples.len(), &frame_probs, VAD_FRAME_SAMPLES, config);
+    info!(
+        "Transcribing {:.1}s of audio in {} chunks",
+        samples.len() as f32 / 16000.0,
+        chunks.len()
+    );
+
+    let mut results = Vec::with_capacity(chunks.len());
+    for (i, c) in chunks.iter().enumerate() {
+        debug!(
+            "chunk {}/{}: {:.1}s-{:.1}s",
+            i + 1,
+            chunks.len(),
+            c.start as f32 / 16000.0,
+            c.end as f32 / 16000.0
+        );
+        results.push(run_inference(engine, &samples[c.start..c.end], language)?);
+    }
+    Ok(chunk::stitch_segments(&chunks, results))
+}
+
+/// Run inference on `samples` with the shared engine.
+/// Empty and hallucinated segments are dropped.
+fn run_inference(
+    engine: &Arc<Mutex<Option<LoadedEngine>>>,
+    samples: &[f32],
+    language: &str,
+) -> Result<Vec<TranscriptSegment>, TranscribeError> {
+    let mut guard = engine
+        .lock()
+        .map_err(|e| TranscribeError::Inference(format!("Lock poisoned: {e}")))?;
+    let loaded = guard.as_mut().ok_or(TranscribeError::NotLoaded)?;
+
+    let mut segments = match &mut loaded.engine {
+        AsrEngine::Parakeet(e) => e.transcribe(samples)?,
+        AsrEngine::Whisper(e) => e.transcribe(samples, language)?,
+    };
+    segments.retain(|s| !s.text.is_empty() && !is_hallucination(&s.text));
+    Ok(segments)
+}
+
 /// Returns true if the text looks like a known ASR hallucination token.
 pub fn is_hallucination(text: &str) -> bool {
     let t = text.trim().to_lowercase();
@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+/// Lowest reported level. Digital silence would be -inf dBFS.
+pub const MIN_DBFS: f32 = -100.0;
+
+/// Default interval between level updates (20 Hz).
+pub const LEVEL_UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Input level of a stretch of audio, for drawing a meter.
+#[derive(Debug, Clone, Copy, PartialEq, uniffi::Record)]
+pub struct InputLevel {
+    /// RMS level in dBFS (a full-scale square wave is 0, a full-scale sine -3).
+    pub rms_dbfs: f32,
+    /// Peak sample level in dBFS.
+    pub peak_dbfs: f32,
+    /// Voice activity probability (0.0-1.0) from the VAD.
+    pub speech_probability: f32,
+}
+
+impl InputLevel {
+    /// Measure `samples` (f32 in -1.0..1.0) along with the VAD's speech probability.
+    pub fn measure(samples: &[f32], speech_probability: f32) -> Self {
+        let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+        let power = if samples.is_empty() {
+            0.0
+        } else {
+            samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32
+        };
+        Self {
+            rms_dbfs: to_dbfs(power.sqrt()),
+            peak_dbfs: to_dbfs(peak),
+            speech_probability,
+        }
+    }
+
+    /// The louder of two levels, field by field.
+    fn max(self, other: Self) -> Self {
+        Self {
+            rms_dbfs: self.rms_dbfs.max(other.rms_dbfs),
+            peak_dbfs: self.peak_dbfs.max(other.peak_dbfs),
+            speech_probability: self.speech_probability.max(other.speech_probability),
+        }
+    }
+}
+
+/// Convert a linear amplitude to dBFS, floored at `MIN_DBFS`.
+pub fn to_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * amplitude.log10()).max(MIN_DBFS)
+}
+
+/// Throttles level updates to one per interval. Levels measured in between
+/// are merged (loudest wins), so short peaks still show up on the meter.
+#[derive(Debug, Clone)]
+pub struct LevelMeter {
+    interval: Duration,
+    last_sent: Option<Instant>,
+    pending: Option<InputLevel>,
+}
+
+impl LevelMeter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: None,
+            pending: None,
+        }
+    }
+
+    /// Add a measurement taken at `now`. Returns the merged level once per
+    /// interval, and None in between.
+    pub fn update(&mut self, level: InputLevel, now: Instant) -> Option<InputLevel> {
+        let merged = match self.pending {
+            Some(pending) => pending.max(level),
+            None => level,
+        };
+        let due = !matches!(self.last_sent, Some(sent) if now.duration_since(sent) < self.interval);
+        if due {
+            self.last_sent = Some(now);
+            self.pending = None;
+            Some(merged)
+        } else {
+            self.pending = Some(merged);
+            None
+        }
+    }
+}
+
+impl Default for LevelMeter {
+    fn default() -> Self {
+        Self::new(LEVEL_UPDATE_INTERVAL)
+    }
+}
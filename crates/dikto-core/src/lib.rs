@@ -1,21 +1,35 @@
 uniffi::setup_scaffolding!();
 
 pub mod audio;
+pub mod catalog;
+pub mod chunk;
 pub mod config;
+pub mod decode;
 pub mod engine;
+pub mod level;
 pub mod models;
+pub mod output;
+pub mod recordings;
+pub mod resample;
+pub mod source;
 pub mod transcribe;
+pub mod utterance;
 pub mod vad;
 
-use audio::{AudioCapture, AudioCaptureConfig, AudioError};
+use audio::{AudioCaptureConfig, AudioError, InputDeviceInfo};
 use config::DiktoConfig;
+use decode::DecodeError;
 use engine::{AsrEngine, AsrSession, LoadedEngine};
-use models::{ModelBackend, ModelError};
-use std::sync::atomic::{AtomicBool, Ordering};
+use level::{InputLevel, LevelMeter};
+use models::{DownloadOptions, ImportMode, ModelBackend, ModelError};
+use output::{OutputFormat, SubtitleOptions};
+use source::{AudioInput, OverflowWarnings};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use tracing::{debug, info, warn};
-use transcribe::{TranscribeConfig, TranscribeError};
+use transcribe::{TranscribeConfig, TranscribeError, TranscriptSegment};
+use utterance::UtteranceTracker;
 use vad::{VadConfig, VadError, VadEvent, VadProcessor};
 
 /// Old Whisper model names (v1) that should be auto-migrated to Parakeet.
@@ -60,32 +74,53 @@ impl From<ModelError> for DiktoError {
         DiktoError::Model(e.to_string())
     }
 }
+impl From<DecodeError> for DiktoError {
+    fn from(e: DecodeError) -> Self {
+        DiktoError::Audio(e.to_string())
+    }
+}
 
 /// Recording state enum.
 #[derive(Debug, Clone, PartialEq, uniffi::Enum)]
 pub enum RecordingState {
     Listening,
     Processing,
-    Done { text: String },
-    Error { message: String },
+    /// `audio_path` is the saved session audio, if `save_audio` was set.
+    Done {
+        text: String,
+        audio_path: Option<String>,
+    },
+    Error {
+        message: String,
+    },
 }
 
 /// Callbacks for transcription events.
 #[uniffi::export(with_foreign)]
 pub trait TranscriptionCallback: Send + Sync {
     fn on_partial(&self, text: String);
-    fn on_final_segment(&self, text: String);
+    /// A final segment with its start/end and word timestamps.
+    fn on_final_segment(&self, segment: TranscriptSegment);
     fn on_silence(&self);
     fn on_error(&self, error: String);
+    /// A problem that doesn't stop the session, e.g. audio input overflow.
+    fn on_warning(&self, message: String);
+    /// Input level and speech probability while listening, about 20 times a second.
+    fn on_level(&self, level: InputLevel);
     fn on_state_change(&self, state: RecordingState);
 }
 
 /// Callbacks for model download progress.
 #[uniffi::export(with_foreign)]
 pub trait DownloadProgressCallback: Send + Sync {
+    /// Progress summed over all files of the model.
     fn on_progress(&self, bytes_downloaded: u64, total_bytes: u64);
+    /// Progress of a single file; files of a model may download in parallel.
+    fn on_file_progress(&self, filename: String, bytes_downloaded: u64, total_bytes: u64);
     fn on_complete(&self, model_name: String);
     fn on_error(&self, error: String);
+    /// The download was stopped via `DownloadHandle::cancel`.
+    fn on_cancelled(&self, model_name: String);
 }
 
 /// Configuration for a listening session.
@@ -95,6 +130,17 @@ pub struct ListenConfig {
     pub max_duration: u32,
     pub silence_duration_ms: u32,
     pub speech_threshold: f32,
+    /// Keep listening after each pause: every utterance is flushed and reported
+    /// via `on_final_segment`, and the session ends only on stop or max duration.
+    pub continuous: bool,
+    /// Save the audio passed to the model as a WAV file in the recordings
+    /// dir; the path is reported in `Done`. In continuous mode the file holds
+    /// all utterances one after another.
+    pub save_audio: bool,
+    /// Most recordings kept after saving (0 = no limit).
+    pub save_audio_max_files: u32,
+    /// Recordings older than this many days are deleted after saving (0 = no limit).
+    pub save_audio_max_age_days: u32,
 }
 
 impl Default for ListenConfig {
@@ -104,6 +150,10 @@ impl Default for ListenConfig {
             max_duration: 30,
             silence_duration_ms: 1500,
             speech_threshold: 0.35,
+            continuous: false,
+            save_audio: false,
+            save_audio_max_files: 50,
+            save_audio_max_age_days: 7,
         }
     }
 }
@@ -115,6 +165,10 @@ impl From<&DiktoConfig> for ListenConfig {
             max_duration: cfg.max_duration,
             silence_duration_ms: cfg.silence_duration_ms,
             speech_threshold: cfg.speech_threshold,
+            continuous: false,
+            save_audio: cfg.save_audio,
+            save_audio_max_files: cfg.save_audio_max_files,
+            save_audio_max_age_days: cfg.save_audio_max_age_days,
         }
     }
 }
@@ -123,6 +177,7 @@ impl From<&DiktoConfig> for ListenConfig {
 #[derive(uniffi::Object)]
 pub struct SessionHandle {
     stop_flag: Arc<AtomicBool>,
+    dropped_samples: Arc<AtomicU64>,
 }
 
 impl SessionHandle {
@@ -130,6 +185,7 @@ impl SessionHandle {
     pub fn new_for_test() -> Self {
         Self {
             stop_flag: Arc::new(AtomicBool::new(false)),
+            dropped_samples: Arc::new(AtomicU64::new(0)),
         }
     }
 }
@@ -145,6 +201,33 @@ impl SessionHandle {
     pub fn is_active(&self) -> bool {
         !self.stop_flag.load(Ordering::Acquire)
     }
+
+    /// Audio samples lost so far because the input overflowed (the pipeline
+    /// fell behind the microphone). Nonzero means gaps in the transcript.
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped_samples.load(Ordering::Acquire)
+    }
+}
+
+/// Handle to cancel a running model download.
+#[derive(uniffi::Object)]
+pub struct DownloadHandle {
+    cancel_flag: Arc<AtomicBool>,
+    active: Arc<AtomicBool>,
+}
+
+#[uniffi::export]
+impl DownloadHandle {
+    /// Cancel the download. The partial file is kept for resuming or deleted,
+    /// per `DiktoConfig.keep_partial_downloads`; `on_cancelled` follows.
+    pub fn cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Release);
+    }
+
+    /// Check if the download is still running.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
 }
 
 /// Owned model info record for FFI.
@@ -155,6 +238,8 @@ pub struct ModelInfoRecord {
     pub description: String,
     pub is_downloaded: bool,
     pub backend: String,
+    /// The model catalog lists a newer version of the installed files.
+    pub update_available: bool,
 }
 
 /// Language info record for FFI.
@@ -217,21 +302,11 @@ impl DiktoEngine {
             .lock()
             .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
         let model_name = inner.config.model_name.clone();
-        let model_info = models::find_model(&model_name).ok_or(DiktoError::NoModel)?;
-        let path = models::model_path(&model_name).ok_or(DiktoError::NoModel)?;
-
-        if !models::is_model_downloaded(&model_name) {
-            return Err(DiktoError::NoModel);
-        }
-
-        let asr = AsrEngine::load(model_info.backend, &path)?;
+        let loaded = load_engine(&model_name)?;
         *inner
             .engine
             .lock()
-            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))? = Some(LoadedEngine {
-            model_name: model_name.clone(),
-            engine: asr,
-        });
+            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))? = Some(loaded);
         info!("Model '{}' loaded and ready", model_name);
         Ok(())
     }
@@ -285,130 +360,20 @@ impl DiktoEngine {
 
     /// Start listening and transcribing. Returns a handle to stop the session.
     /// Lazy-loads the model into RAM if not already loaded.
-    /// The final result is delivered via the callback's on_state_change(Done { text }).
+    /// The final result is delivered via the callback's on_state_change(Done { text, .. }).
     pub fn start_listening(
         &self,
         listen_config: ListenConfig,
         callback: Arc<dyn TranscriptionCallback>,
     ) -> Result<Arc<SessionHandle>, DiktoError> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
-
-        if inner.recording.load(Ordering::Acquire) {
-            return Err(DiktoError::AlreadyRecording);
-        }
-
-        // Verify model is available on disk
-        let model_name = inner.config.model_name.clone();
-        let model_info = models::find_model(&model_name).ok_or(DiktoError::NoModel)?;
-        if !models::is_model_downloaded(&model_name) {
-            return Err(DiktoError::NoModel);
-        }
-
-        let engine_holder = inner.engine.clone();
-        let backend = model_info.backend;
-        let model_path = models::model_path(&model_name).ok_or(DiktoError::NoModel)?;
-
-        let stop_flag = Arc::new(AtomicBool::new(false));
-        let handle = Arc::new(SessionHandle {
-            stop_flag: stop_flag.clone(),
+        let config = self.get_config();
+        let input = AudioInput::Microphone(AudioCaptureConfig {
+            device_name: config.input_device,
+            input_channels: config.input_channels,
+            resample_quality: config.resample_quality,
+            ..Default::default()
         });
-
-        let recording = inner.recording.clone();
-        recording.store(true, Ordering::Release);
-
-        let max_duration = listen_config.max_duration;
-        let silence_duration_ms = listen_config.silence_duration_ms;
-        let speech_threshold = listen_config.speech_threshold;
-        let language = listen_config.language.clone();
-
-        drop(inner); // Release outer lock before spawning
-
-        std::thread::spawn(move || {
-            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                // Lazy-load model if needed
-                let needs_load = {
-                    let guard = engine_holder
-                        .lock()
-                        .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
-                    !matches!(&*guard, Some(loaded) if loaded.model_name == model_name)
-                };
-
-                if needs_load {
-                    callback.on_state_change(RecordingState::Processing);
-                    callback.on_partial("Loading model...".to_string());
-                    debug!("Lazy-loading model '{}'...", model_name);
-
-                    match AsrEngine::load(backend, &model_path) {
-                        Ok(asr) => {
-                            let mut guard = engine_holder
-                                .lock()
-                                .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
-                            *guard = Some(LoadedEngine {
-                                model_name: model_name.clone(),
-                                engine: asr,
-                            });
-                            debug!("Model '{}' loaded into RAM", model_name);
-                        }
-                        Err(e) => {
-                            recording.store(false, Ordering::Release);
-                            callback.on_state_change(RecordingState::Error {
-                                message: format!("Failed to load model: {e}"),
-                            });
-                            return Ok(());
-                        }
-                    }
-                }
-
-                // Create transcription session
-                let transcribe_config = TranscribeConfig { language };
-                let session = {
-                    let guard = engine_holder
-                        .lock()
-                        .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
-                    let loaded = guard.as_ref().ok_or(DiktoError::NoModel)?;
-                    loaded.engine.create_session(transcribe_config)
-                };
-
-                let result = run_pipeline(
-                    session,
-                    &engine_holder,
-                    stop_flag,
-                    callback.clone(),
-                    max_duration,
-                    silence_duration_ms,
-                    speech_threshold,
-                );
-
-                recording.store(false, Ordering::Release);
-
-                match &result {
-                    Ok(text) => {
-                        debug!("pipeline done, text_len={}", text.len());
-                        callback.on_state_change(RecordingState::Done { text: text.clone() });
-                    }
-                    Err(e) => {
-                        warn!("pipeline error: {e}");
-                        callback.on_state_change(RecordingState::Error {
-                            message: e.to_string(),
-                        });
-                    }
-                }
-
-                Ok::<(), DiktoError>(())
-            }));
-
-            if let Err(_panic) = result {
-                recording.store(false, Ordering::Release);
-                callback.on_state_change(RecordingState::Error {
-                    message: "Internal error (thread panic)".to_string(),
-                });
-            }
-        });
-
-        Ok(handle)
+        self.start_listening_with_input(listen_config, input, callback)
     }
 
     /// Get a copy of the current config.
@@ -446,20 +411,100 @@ impl DiktoEngine {
                     ModelBackend::Parakeet => "Parakeet".to_string(),
                     ModelBackend::Whisper => "Whisper".to_string(),
                 },
+                update_available: models::update_available(m.name),
             })
             .collect()
     }
 
+    /// Install a model from files already on disk (a model directory, or the
+    /// ggml `.bin` of a Whisper model) instead of downloading it. Files are
+    /// checked against their known SHA-256 and copied, or symlinked if
+    /// `symlink` is set. Blocks while hashing; call off the main thread.
+    pub fn import_model(
+        &self,
+        model_name: String,
+        source_path: String,
+        symlink: bool,
+    ) -> Result<(), DiktoError> {
+        let mode = if symlink {
+            ImportMode::Symlink
+        } else {
+            ImportMode::Copy
+        };
+        models::import_model(&model_name, std::path::Path::new(&source_path), mode)?;
+        Ok(())
+    }
+
+    /// Delete a downloaded model's files. The configured model can't be
+    /// deleted; switch to another model first.
+    pub fn delete_model(&self, model_name: String) -> Result<(), DiktoError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
+        if inner.config.model_name == model_name {
+            return Err(DiktoError::Model(format!(
+                "Cannot delete '{model_name}': it is the configured model"
+            )));
+        }
+        // Drop the model from RAM if a request loaded it
+        if let Ok(mut engine) = inner.engine.lock() {
+            if engine.as_ref().is_some_and(|e| e.model_name == model_name) {
+                *engine = None;
+            }
+        }
+        drop(inner);
+
+        models::delete_model(&model_name)?;
+        Ok(())
+    }
+
+    /// Re-hash the installed files of a model and report missing, corrupt
+    /// and extra files. Blocks while hashing; call off the main thread.
+    pub fn verify_model(&self, model_name: String) -> Result<models::VerifyReport, DiktoError> {
+        Ok(models::verify_model(&model_name)?)
+    }
+
+    /// Fetch the model catalog from the configured `catalog_url` and cache
+    /// it, so `list_models` shows its models and available updates. Returns
+    /// the number of catalog models; 0 if no catalog is configured. Blocks
+    /// on the network; call off the main thread.
+    pub fn refresh_catalog(&self) -> Result<u32, DiktoError> {
+        let config = self.get_config();
+        let Some(url) = config.catalog_url.clone() else {
+            return Ok(0);
+        };
+        let options = DownloadOptions::from_config(&config);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| DiktoError::Model(format!("Failed to create runtime: {e}")))?;
+        let catalog = rt.block_on(catalog::refresh_catalog(
+            &url,
+            config.catalog_public_key.as_deref(),
+            &options,
+        ))?;
+        Ok(catalog.len() as u32)
+    }
+
     /// Download a model with progress reporting via callback.
+    /// Returns a handle to cancel the download.
     pub fn download_model(
         &self,
         model_name: String,
         callback: Arc<dyn DownloadProgressCallback>,
-    ) -> Result<(), DiktoError> {
+    ) -> Result<Arc<DownloadHandle>, DiktoError> {
         // Verify model exists
         let _ = models::find_model(&model_name)
             .ok_or_else(|| DiktoError::Model(format!("Unknown model: {model_name}")))?;
 
+        let options = DownloadOptions::from_config(&self.get_config());
+        let active = Arc::new(AtomicBool::new(true));
+        let handle = Arc::new(DownloadHandle {
+            cancel_flag: options.cancel.clone(),
+            active: active.clone(),
+        });
+
         let name = model_name.clone();
         std::thread::spawn(move || {
             let rt = match tokio::runtime::Builder::new_current_thread()
@@ -468,6 +513,7 @@ impl DiktoEngine {
             {
                 Ok(rt) => rt,
                 Err(e) => {
+                    active.store(false, Ordering::Release);
                     callback.on_error(format!("Failed to create runtime: {e}"));
                     return;
                 }
@@ -475,18 +521,32 @@ impl DiktoEngine {
 
             rt.block_on(async {
                 let cb = callback.clone();
-                match models::download_model(&name, move |downloaded, total| {
-                    cb.on_progress(downloaded, total);
-                })
-                .await
-                {
+                let file_cb = callback.clone();
+                let result = models::download_model_with_options(
+                    &name,
+                    &options,
+                    move |downloaded, total| {
+                        cb.on_progress(downloaded, total);
+                    },
+                    move |file| {
+                        file_cb.on_file_progress(
+                            file.filename.to_string(),
+                            file.downloaded,
+                            file.total,
+                        );
+                    },
+                )
+                .await;
+                active.store(false, Ordering::Release);
+                match result {
                     Ok(_) => callback.on_complete(name),
+                    Err(ModelError::Cancelled) => callback.on_cancelled(name),
                     Err(e) => callback.on_error(e.to_string()),
                 }
             });
         });
 
-        Ok(())
+        Ok(handle)
     }
 
     /// Get available languages for the currently configured model.
@@ -552,40 +612,287 @@ impl DiktoEngine {
     }
 }
 
+impl DiktoEngine {
+    /// Like `start_listening`, but reads audio from any `AudioInput`
+    /// (microphone, file, stdin PCM, in-memory samples or a custom source).
+    /// The session ends when the input is exhausted, on silence, on stop or at max duration.
+    pub fn start_listening_with_input(
+        &self,
+        listen_config: ListenConfig,
+        input: AudioInput,
+        callback: Arc<dyn TranscriptionCallback>,
+    ) -> Result<Arc<SessionHandle>, DiktoError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
+
+        if inner.recording.load(Ordering::Acquire) {
+            return Err(DiktoError::AlreadyRecording);
+        }
+
+        // Verify model is available on disk
+        let model_name = inner.config.model_name.clone();
+        models::find_model(&model_name).ok_or(DiktoError::NoModel)?;
+        if !models::is_model_downloaded(&model_name) {
+            return Err(DiktoError::NoModel);
+        }
+
+        let engine_holder = inner.engine.clone();
+        let recording = inner.recording.clone();
+        recording.store(true, Ordering::Release);
+
+        drop(inner); // Release outer lock before spawning
+
+        Ok(spawn_session(
+            engine_holder,
+            model_name,
+            listen_config,
+            input,
+            callback,
+            recording,
+        ))
+    }
+}
+
+/// List the available audio input devices, for `DiktoConfig.input_device`.
+#[uniffi::export]
+pub fn list_input_devices() -> Vec<InputDeviceInfo> {
+    audio::list_input_devices()
+}
+
+/// Render transcript segments as plain text, SRT, WebVTT or JSON.
+#[uniffi::export]
+pub fn format_transcript(
+    segments: Vec<TranscriptSegment>,
+    format: OutputFormat,
+    language: String,
+    model_name: String,
+    options: SubtitleOptions,
+) -> String {
+    output::format_transcript(&segments, format, &language, &model_name, &options)
+}
+
+/// Load a downloaded model by name into RAM.
+/// Used by the CLI and other callers that manage their own engine holder.
+pub fn load_engine(model_name: &str) -> Result<LoadedEngine, DiktoError> {
+    let model_info = models::find_model(model_name).ok_or(DiktoError::NoModel)?;
+    let path = models::model_path(model_name).ok_or(DiktoError::NoModel)?;
+
+    if !models::is_model_downloaded(model_name) {
+        return Err(DiktoError::NoModel);
+    }
+
+    let asr = AsrEngine::load(model_info.backend, &path)?;
+    Ok(LoadedEngine {
+        model_name: model_name.to_string(),
+        engine: asr,
+    })
+}
+
+/// Make sure `model_name` is the model loaded in `engine_holder`, replacing any
+/// other loaded model. `on_load` is called before a (slow) load starts; nothing
+/// happens if the model is already in RAM. The lock is not held while loading.
+pub fn ensure_engine_loaded(
+    engine_holder: &Arc<Mutex<Option<LoadedEngine>>>,
+    model_name: &str,
+    on_load: impl FnOnce(),
+) -> Result<(), DiktoError> {
+    let needs_load = {
+        let guard = engine_holder
+            .lock()
+            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
+        !matches!(&*guard, Some(loaded) if loaded.model_name == model_name)
+    };
+
+    if needs_load {
+        on_load();
+        debug!("Lazy-loading model '{}'...", model_name);
+        let loaded = load_engine(model_name)?;
+        let mut guard = engine_holder
+            .lock()
+            .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
+        *guard = Some(loaded);
+        debug!("Model '{}' loaded into RAM", model_name);
+    }
+    Ok(())
+}
+
+/// Saved session audio is capped at the longest single session (1 hour,
+/// about 230 MB as float WAV), since it is kept in memory until the end.
+const MAX_SAVED_AUDIO_SAMPLES: usize = config::MAX_DURATION_SECS as usize * 16000;
+
+/// Run a recording + transcription session for `input` on a background thread,
+/// using (and lazily loading) `model_name` in the shared `engine_holder`.
+/// `recording` is cleared when the session ends. Unlike
+/// `DiktoEngine::start_listening_with_input`, several sessions may run at once
+/// (e.g. one per server connection); they share the loaded model.
+pub fn spawn_session(
+    engine_holder: Arc<Mutex<Option<LoadedEngine>>>,
+    model_name: String,
+    listen_config: ListenConfig,
+    input: AudioInput,
+    callback: Arc<dyn TranscriptionCallback>,
+    recording: Arc<AtomicBool>,
+) -> Arc<SessionHandle> {
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let dropped_samples = Arc::new(AtomicU64::new(0));
+    let handle = Arc::new(SessionHandle {
+        stop_flag: stop_flag.clone(),
+        dropped_samples: dropped_samples.clone(),
+    });
+    recording.store(true, Ordering::Release);
+
+    std::thread::spawn(move || {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            // Lazy-load model if needed
+            let loaded = ensure_engine_loaded(&engine_holder, &model_name, || {
+                callback.on_state_change(RecordingState::Processing);
+                callback.on_partial("Loading model...".to_string());
+            });
+            if let Err(e) = loaded {
+                recording.store(false, Ordering::Release);
+                callback.on_state_change(RecordingState::Error {
+                    message: e.to_string(),
+                });
+                return Ok(());
+            }
+
+            // Create transcription session
+            let transcribe_config = TranscribeConfig {
+                language: listen_config.language.clone(),
+            };
+            let mut session = {
+                let guard = engine_holder
+                    .lock()
+                    .map_err(|e| DiktoError::Config(format!("Lock poisoned: {e}")))?;
+                let loaded = guard.as_ref().ok_or(DiktoError::NoModel)?;
+                loaded.engine.create_session(transcribe_config)
+            };
+
+            if listen_config.save_audio {
+                session.keep_flushed_audio(MAX_SAVED_AUDIO_SAMPLES);
+            }
+            let result = run_pipeline(
+                &mut session,
+                &engine_holder,
+                input,
+                stop_flag,
+                &dropped_samples,
+                callback.clone(),
+                &listen_config,
+            );
+
+            recording.store(false, Ordering::Release);
+
+            match &result {
+                Ok(text) => {
+                    debug!("pipeline done, text_len={}", text.len());
+                    let audio_path = save_session_audio(&mut session, &listen_config, &*callback);
+                    callback.on_state_change(RecordingState::Done {
+                        text: text.clone(),
+                        audio_path,
+                    });
+                }
+                Err(e) => {
+                    warn!("pipeline error: {e}");
+                    callback.on_state_change(RecordingState::Error {
+                        message: e.to_string(),
+                    });
+                }
+            }
+
+            Ok::<(), DiktoError>(())
+        }));
+
+        if let Err(_panic) = result {
+            recording.store(false, Ordering::Release);
+            callback.on_state_change(RecordingState::Error {
+                message: "Internal error (thread panic)".to_string(),
+            });
+        }
+    });
+
+    handle
+}
+
+/// Save the audio the session passed to the model (if enabled) and prune old
+/// recordings. Returns the file path; failures are reported as warnings.
+fn save_session_audio(
+    session: &mut AsrSession,
+    listen_config: &ListenConfig,
+    callback: &dyn TranscriptionCallback,
+) -> Option<String> {
+    let samples = session.take_flushed_audio().filter(|s| !s.is_empty())?;
+    let dir = match config::recordings_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            callback.on_warning(format!("Could not save session audio: {e}"));
+            return None;
+        }
+    };
+    let path = match recordings::save_recording(&dir, &samples) {
+        Ok(path) => path,
+        Err(e) => {
+            warn!("Failed to save session audio: {e}");
+            callback.on_warning(format!("Could not save session audio: {e}"));
+            return None;
+        }
+    };
+    if let Err(e) = recordings::prune_recordings(
+        &dir,
+        listen_config.save_audio_max_files,
+        listen_config.save_audio_max_age_days,
+        std::time::SystemTime::now(),
+    ) {
+        warn!("Failed to prune recordings in {}: {e}", dir.display());
+    }
+    Some(path.display().to_string())
+}
+
 /// The main recording + transcription pipeline, runs on a background thread.
 fn run_pipeline(
-    mut session: AsrSession,
+    session: &mut AsrSession,
     engine: &Arc<Mutex<Option<LoadedEngine>>>,
+    input: AudioInput,
     stop_flag: Arc<AtomicBool>,
+    dropped_samples: &AtomicU64,
     callback: Arc<dyn TranscriptionCallback>,
-    max_duration: u32,
-    silence_duration_ms: u32,
-    speech_threshold: f32,
+    listen_config: &ListenConfig,
 ) -> Result<String, DiktoError> {
     callback.on_state_change(RecordingState::Listening);
 
-    // Start audio capture
-    let mut capture = AudioCapture::start(AudioCaptureConfig::default())?;
+    // Open the audio source (mic capture, file, stdin, ...)
+    let mut source = input.open()?;
 
     // Initialize VAD
     let vad_config = VadConfig {
-        speech_threshold,
-        silence_duration_ms,
+        speech_threshold: listen_config.speech_threshold,
+        silence_duration_ms: listen_config.silence_duration_ms,
         ..Default::default()
     };
     let mut vad = VadProcessor::new(vad_config)?;
     let chunk_size = vad.chunk_size();
 
     let start_time = std::time::Instant::now();
-    let max_dur = std::time::Duration::from_secs(max_duration as u64);
+    let max_dur = std::time::Duration::from_secs(listen_config.max_duration as u64);
 
     let mut vad_buffer: Vec<f32> = Vec::new();
-    let mut speech_detected = false;
     // Buffer ~1s of pre-speech audio so we don't lose the start of speech
     let pre_speech_max = 16000usize; // 1 second at 16kHz
     let mut pre_speech_buffer: Vec<f32> = Vec::new();
     // Throttle overlay updates to every ~500ms
     let mut last_partial_time = std::time::Instant::now();
+    let mut has_partial_text = false;
+    // Samples read from the source so far, and where the current utterance's
+    // audio starts, so segment times are relative to the start of the session
+    let mut total_read = 0usize;
+    let mut utterance = UtteranceTracker::new();
+    // Text of finished utterances (continuous mode)
+    let mut texts: Vec<String> = Vec::new();
+    // Input overflow warnings are throttled; the total is on the session handle
+    let mut overflow_warnings = OverflowWarnings::new();
+    let mut level_meter = LevelMeter::default();
 
     loop {
         // Check stop conditions
@@ -598,12 +905,24 @@ fn run_pipeline(
             break;
         }
 
-        // Read samples from mic
-        let samples = capture.read_samples();
+        // Read samples from the source
+        let samples = source.read_samples();
+        let dropped = source.dropped_samples();
+        dropped_samples.store(dropped, Ordering::Release);
+        if let Some(message) = overflow_warnings.check(dropped, std::time::Instant::now()) {
+            warn!("{message}");
+            callback.on_warning(message);
+        }
         if samples.is_empty() {
+            if source.is_finished() {
+                info!("Audio source finished");
+                break;
+            }
             std::thread::sleep(std::time::Duration::from_millis(10));
             continue;
         }
+        let read_start = total_read;
+        total_read += samples.len();
 
         // Feed to VAD in chunks
         vad_buffer.extend_from_slice(&samples);
@@ -611,39 +930,45 @@ fn run_pipeline(
         while vad_buffer.len() >= chunk_size {
             let chunk: Vec<f32> = vad_buffer.drain(..chunk_size).collect();
 
-            match vad.process_chunk(&chunk)? {
+            let event = vad.process_chunk(&chunk)?;
+            let level = InputLevel::measure(&chunk, vad.last_probability());
+            if let Some(level) = level_meter.update(level, std::time::Instant::now()) {
+                callback.on_level(level);
+            }
+
+            match event {
                 VadEvent::SpeechStart => {
-                    speech_detected = true;
-                    debug!(
-                        "Speech detected, feeding {} pre-speech samples",
-                        pre_speech_buffer.len()
-                    );
                     // Feed buffered pre-speech audio so transcription captures the start
-                    if !pre_speech_buffer.is_empty() {
+                    if utterance.speech_start(read_start - pre_speech_buffer.len()) {
+                        debug!(
+                            "Speech detected, feeding {} pre-speech samples",
+                            pre_speech_buffer.len()
+                        );
                         session.feed_samples(&pre_speech_buffer);
-                        pre_speech_buffer.clear();
                     }
+                    pre_speech_buffer.clear();
                 }
                 VadEvent::SpeechEnd => {
-                    if speech_detected {
+                    if utterance.is_active() && listen_config.continuous {
+                        // Finish this utterance and keep listening
+                        callback.on_silence();
+                        info!("Utterance ended, continuing to listen");
+                        let final_segments = session.flush(engine)?;
+                        let start = utterance.finish();
+                        emit_final_segments(final_segments, start, &callback, &mut texts);
+                        has_partial_text = false;
+                    } else if utterance.is_active() {
                         callback.on_silence();
                         info!("Speech ended (silence detected)");
 
                         // Flush remaining audio — batch inference happens here
                         callback.on_state_change(RecordingState::Processing);
                         let final_segments = session.flush(engine)?;
-                        let text = final_segments
-                            .iter()
-                            .map(|s| s.text.as_str())
-                            .collect::<Vec<_>>()
-                            .join(" ");
-
-                        for seg in &final_segments {
-                            callback.on_final_segment(seg.text.clone());
-                        }
-
-                        capture.stop();
-                        return Ok(text);
+                        let start = utterance.finish();
+                        emit_final_segments(final_segments, start, &callback, &mut texts);
+
+                        source.stop();
+                        return Ok(texts.join(" "));
                     }
                 }
                 VadEvent::SpeechContinue | VadEvent::Silence => {}
@@ -651,11 +976,22 @@ fn run_pipeline(
         }
 
         // Feed audio to transcription buffer or buffer pre-speech audio
-        if speech_detected {
+        if utterance.is_active() {
             session.feed_samples(&samples);
 
-            // Send "Recording..." status to overlay (throttled)
-            if last_partial_time.elapsed() >= std::time::Duration::from_millis(500) {
+            // Live partial transcript (self-throttled); until the first one
+            // arrives, show "Recording..." status instead (throttled)
+            match session.partial(engine) {
+                Ok(Some(segment)) => {
+                    callback.on_partial(segment.text);
+                    has_partial_text = true;
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Partial transcription failed: {e}"),
+            }
+            if !has_partial_text
+                && last_partial_time.elapsed() >= std::time::Duration::from_millis(500)
+            {
                 let duration = session.buffer_duration_secs();
                 callback.on_partial(format!("Recording... ({duration:.1}s)"));
                 last_partial_time = std::time::Instant::now();
@@ -673,18 +1009,25 @@ fn run_pipeline(
     // Flush on stop
     callback.on_state_change(RecordingState::Processing);
     let final_segments = session.flush(engine)?;
-    let text = final_segments
-        .iter()
-        .map(|s| s.text.as_str())
-        .collect::<Vec<_>>()
-        .join(" ");
+    emit_final_segments(final_segments, utterance.finish(), &callback, &mut texts);
 
-    for seg in &final_segments {
-        callback.on_final_segment(seg.text.clone());
-    }
+    source.stop();
+    Ok(texts.join(" "))
+}
 
-    capture.stop();
-    Ok(text)
+/// Shift an utterance's segments to session time, report them via
+/// `on_final_segment` and collect their text.
+fn emit_final_segments(
+    segments: Vec<TranscriptSegment>,
+    utterance_start: usize,
+    callback: &Arc<dyn TranscriptionCallback>,
+    texts: &mut Vec<String>,
+) {
+    for mut seg in segments {
+        seg.offset(utterance_start as f64 / 16000.0);
+        texts.push(seg.text.clone());
+        callback.on_final_segment(seg);
+    }
 }
 
 /// Parakeet TDT v3 supported languages (25 European languages).
@@ -1,6 +1,12 @@
-use crate::config::models_dir;
+use crate::catalog;
+use crate::config::{models_dir, models_manifest_path, DiktoConfig};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use tracing::{info, warn};
 
@@ -14,17 +20,27 @@ pub enum ModelError {
     Io(#[from] std::io::Error),
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
+    #[error("Invalid model manifest: {0}")]
+    InvalidManifest(String),
+    #[error("Invalid model catalog: {0}")]
+    InvalidCatalog(String),
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+    #[error("Download failed: HTTP {0} for {1}")]
+    HttpStatus(u16, String),
+    #[error("Download cancelled")]
+    Cancelled,
 }
 
 /// ASR backend type for a model.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModelBackend {
     Parakeet,
     Whisper,
 }
 
 /// A single file that is part of a model.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ModelFile {
     pub filename: &'static str,
     pub url: &'static str,
@@ -34,7 +50,7 @@ pub struct ModelFile {
 }
 
 /// Model registry entry. A model is a directory containing multiple files.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ModelInfo {
     pub name: &'static str,
     pub size_mb: u32,
@@ -159,9 +175,244 @@ pub const MODELS: &[ModelInfo] = &[
     },
 ];
 
-/// Look up model info by name.
+/// A file entry in the user model manifest.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    filename: String,
+    url: String,
+    #[serde(default)]
+    size_mb: u32,
+    #[serde(default)]
+    sha256: String,
+}
+
+/// A model entry in the user model manifest.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestModel {
+    name: String,
+    backend: String,
+    #[serde(default)]
+    description: String,
+    /// Defaults to the sum of the file sizes.
+    size_mb: Option<u32>,
+    files: Vec<ManifestFile>,
+}
+
+/// User model manifest (`~/.config/dikto/models.json`).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    models: Vec<ManifestModel>,
+}
+
+/// The two JSON model lists, which share a format but not all rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ModelList {
+    /// The user manifest: may not reuse built-in names; hashes are optional.
+    Manifest,
+    /// The remote catalog: entries with a built-in name update that model;
+    /// every file needs a hash.
+    Catalog,
+}
+
+/// Parse and validate a user model manifest. Every entry must have a unique
+/// name that doesn't shadow a built-in model, a known backend, at least one
+/// file with a plain filename and an http(s) URL, and (if given) a 64-digit
+/// hex SHA-256. The first invalid entry fails the whole manifest.
+pub fn parse_manifest(json: &str) -> Result<Vec<ModelInfo>, ModelError> {
+    parse_model_list(json, ModelList::Manifest).map_err(ModelError::InvalidManifest)
+}
+
+/// Parse and validate a manifest or catalog; see `parse_manifest`.
+pub(crate) fn parse_model_list(json: &str, list: ModelList) -> Result<Vec<ModelInfo>, String> {
+    let manifest: Manifest = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+    let mut models: Vec<ModelInfo> = Vec::with_capacity(manifest.models.len());
+    for (i, entry) in manifest.models.into_iter().enumerate() {
+        let invalid = |msg: String| format!("model #{} ('{}'): {msg}", i + 1, entry.name);
+
+        if !is_plain_name(&entry.name) {
+            return Err(invalid(
+                "name must be non-empty and use only letters, digits, '.', '_' or '-'".to_string(),
+            ));
+        }
+        if list == ModelList::Manifest && MODELS.iter().any(|m| m.name == entry.name) {
+            return Err(invalid(
+                "name is already used by a built-in model".to_string(),
+            ));
+        }
+        if models.iter().any(|m| m.name == entry.name) {
+            return Err(invalid("duplicate model name".to_string()));
+        }
+        let backend = match entry.backend.to_lowercase().as_str() {
+            "parakeet" => ModelBackend::Parakeet,
+            "whisper" => ModelBackend::Whisper,
+            other => {
+                return Err(invalid(format!(
+                    "unknown backend '{other}' (expected 'parakeet' or 'whisper')"
+                )))
+            }
+        };
+        if entry.files.is_empty() {
+            return Err(invalid("no files listed".to_string()));
+        }
+        if backend == ModelBackend::Whisper
+            && !entry.files.iter().any(|f| f.filename.ends_with(".bin"))
+        {
+            return Err(invalid("whisper models need a ggml .bin file".to_string()));
+        }
+
+        let mut files = Vec::with_capacity(entry.files.len());
+        for file in &entry.files {
+            if !is_plain_name(&file.filename) {
+                return Err(invalid(format!("invalid filename '{}'", file.filename)));
+            }
+            if files
+                .iter()
+                .any(|f: &ModelFile| f.filename == file.filename)
+            {
+                return Err(invalid(format!("duplicate file '{}'", file.filename)));
+            }
+            if !(file.url.starts_with("https://") || file.url.starts_with("http://")) {
+                return Err(invalid(format!(
+                    "URL for '{}' must start with https:// or http://",
+                    file.filename
+                )));
+            }
+            let sha256 = file.sha256.to_lowercase();
+            if list == ModelList::Catalog && sha256.is_empty() {
+                return Err(invalid(format!(
+                    "sha256 for '{}' is required",
+                    file.filename
+                )));
+            }
+            if !sha256.is_empty() && !is_sha256_hex(&sha256) {
+                return Err(invalid(format!(
+                    "sha256 for '{}' must be 64 hex characters",
+                    file.filename
+                )));
+            }
+            files.push(ModelFile {
+                filename: intern_str(&file.filename),
+                url: intern_str(&file.url),
+                size_mb: file.size_mb,
+                sha256: intern_str(&sha256),
+            });
+        }
+
+        let size_mb = entry
+            .size_mb
+            .unwrap_or_else(|| files.iter().map(|f| f.size_mb).sum());
+        models.push(ModelInfo {
+            name: intern_str(&entry.name),
+            size_mb,
+            description: intern_str(&entry.description),
+            files: intern(&INTERNED_FILES, files.into_boxed_slice()),
+            backend,
+        });
+    }
+    Ok(models)
+}
+
+/// Read and validate the manifest at `path`. A missing file means no custom models.
+pub fn load_manifest(path: &Path) -> Result<Vec<ModelInfo>, ModelError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => parse_manifest(&json),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(ModelError::Io(e)),
+    }
+}
+
+/// Models from a JSON list, cached until one of the files it was loaded
+/// from changes.
+/// The models are interned, so they can be handed out as `&'static
+/// ModelInfo` like the built-ins; reloading a list seen before (e.g. after a
+/// touch or a refresh without changes) allocates nothing new.
+pub(crate) struct ModelListCache {
+    stamps: Vec<Option<(SystemTime, u64)>>,
+    models: &'static [ModelInfo],
+}
+
+/// Models loaded from `path` with `load`, reusing `cache` while the mtime and
+/// size of `path` and of the other files `load` reads (`inputs`) are
+/// unchanged. Load errors are logged and give no models.
+pub(crate) fn cached_model_list(
+    cache: &Mutex<Option<ModelListCache>>,
+    path: &Path,
+    inputs: &[&Path],
+    load: fn(&Path) -> Result<Vec<ModelInfo>, ModelError>,
+) -> &'static [ModelInfo] {
+    let stamps: Vec<_> = std::iter::once(path)
+        .chain(inputs.iter().copied())
+        .map(|p| {
+            std::fs::metadata(p)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len())))
+        })
+        .collect();
+
+    let Ok(mut cache) = cache.lock() else {
+        return &[];
+    };
+    if let Some(cached) = cache.as_ref() {
+        if cached.stamps == stamps {
+            return cached.models;
+        }
+    }
+
+    let models: &'static [ModelInfo] = match load(path) {
+        Ok(models) => {
+            if !models.is_empty() {
+                info!("Loaded {} model(s) from {}", models.len(), path.display());
+            }
+            intern(&INTERNED_LISTS, models.into_boxed_slice())
+        }
+        Err(e) => {
+            warn!("Ignoring {}: {e}", path.display());
+            &[]
+        }
+    };
+    *cache = Some(ModelListCache { stamps, models });
+    models
+}
+
+static MANIFEST_CACHE: Mutex<Option<ModelListCache>> = Mutex::new(None);
+
+/// Models from the user manifest. An invalid manifest is logged and ignored.
+pub fn custom_models() -> &'static [ModelInfo] {
+    match models_manifest_path() {
+        Ok(path) => cached_model_list(&MANIFEST_CACHE, &path, &[], load_manifest),
+        Err(_) => &[],
+    }
+}
+
+/// All known models: the built-in registry (with updates from the catalog
+/// applied), the user manifest, then models only listed in the catalog.
+pub fn all_models() -> impl Iterator<Item = &'static ModelInfo> {
+    let catalog = catalog::catalog_models();
+    let custom = custom_models();
+    let builtins = MODELS
+        .iter()
+        .map(move |m| catalog.iter().find(|c| c.name == m.name).unwrap_or(m));
+    let added = catalog.iter().filter(move |c| {
+        !MODELS.iter().any(|m| m.name == c.name) && !custom.iter().any(|m| m.name == c.name)
+    });
+    builtins.chain(custom.iter()).chain(added)
+}
+
+/// Model definition without catalog updates: the built-in or manifest entry.
+fn base_model(name: &str) -> Option<&'static ModelInfo> {
+    MODELS
+        .iter()
+        .chain(custom_models().iter())
+        .find(|m| m.name == name)
+}
+
+/// Look up model info by name (built-in, from the user manifest or the catalog).
 pub fn find_model(name: &str) -> Option<&'static ModelInfo> {
-    MODELS.iter().find(|m| m.name == name)
+    all_models().find(|m| m.name == name)
 }
 
 /// Get the local directory path for a model.
@@ -169,148 +420,721 @@ pub fn model_path(name: &str) -> Option<PathBuf> {
     find_model(name).map(|_| models_dir().join(name))
 }
 
-/// Check if all files of a model are downloaded.
+/// Check if all files of a model are downloaded. This is about the version
+/// that was installed, not the catalog's: a newer version with other files
+/// is an update (see `update_available`), not a missing download.
 pub fn is_model_downloaded(name: &str) -> bool {
     let Some(model) = find_model(name) else {
         return false;
     };
-    let dir = models_dir().join(name);
-    model.files.iter().all(|f| dir.join(f.filename).exists())
+    is_installed_in(&models_dir().join(name), base_model(name).unwrap_or(model))
+}
+
+/// Whether all files installed in `dir` are there: those of its install
+/// record, or without one those of `fallback` (the definition the files were
+/// installed from).
+pub fn is_installed_in(dir: &Path, fallback: &ModelInfo) -> bool {
+    let installed = installed_files(dir, Some(fallback)).unwrap_or_default();
+    !installed.is_empty() && installed.keys().all(|f| dir.join(f).exists())
 }
 
 /// List all models with their download status.
 pub fn list_models() -> Vec<(ModelInfo, bool)> {
-    MODELS
-        .iter()
+    all_models()
         .map(|m| (m.clone(), is_model_downloaded(m.name)))
         .collect()
 }
 
+fn not_found(name: &str) -> ModelError {
+    let available = all_models().map(|m| m.name).collect::<Vec<_>>().join(", ");
+    ModelError::NotFound(name.to_string(), available)
+}
+
+/// Names used as directory or file names: no separators or `..`.
+pub fn is_plain_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+fn is_sha256_hex(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Models from the manifest and the catalog must be `&'static` like the
+// built-ins, so their parts are leaked. Interning them bounds the leak by the
+// distinct definitions ever loaded, not by how often the files are reloaded.
+type InternPool<T> = LazyLock<Mutex<HashSet<&'static T>>>;
+
+static INTERNED_STRS: InternPool<str> = LazyLock::new(Default::default);
+static INTERNED_FILES: InternPool<[ModelFile]> = LazyLock::new(Default::default);
+static INTERNED_LISTS: InternPool<[ModelInfo]> = LazyLock::new(Default::default);
+
+/// The leaked copy of `value`, reusing an equal one leaked before.
+fn intern<T: ?Sized + Eq + std::hash::Hash>(pool: &InternPool<T>, value: Box<T>) -> &'static T {
+    let mut pool = pool.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(interned) = pool.get(&*value) {
+        return interned;
+    }
+    let leaked: &'static T = Box::leak(value);
+    pool.insert(leaked);
+    leaked
+}
+
+fn intern_str(s: &str) -> &'static str {
+    intern(&INTERNED_STRS, s.into())
+}
+
+/// How often a stalled download checks for cancellation.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Upper bound for the delay between download retries.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Environment variable naming a Hugging Face mirror, as used by the HF tools.
+pub const HF_ENDPOINT_ENV: &str = "HF_ENDPOINT";
+
+/// Options for model downloads.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// Set to true to abort the download with `ModelError::Cancelled`.
+    pub cancel: Arc<AtomicBool>,
+    /// Keep `<file>.downloading` partial files when cancelled, so the next
+    /// download resumes; otherwise they are deleted.
+    pub keep_partial_on_cancel: bool,
+    /// How often a file is retried after a network error, timeout, or HTTP
+    /// 408/429/5xx. Retries resume the partial file.
+    pub retries: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub initial_backoff: Duration,
+    /// Limit for establishing a connection.
+    pub connect_timeout: Duration,
+    /// Limit for a single read; a stalled transfer fails (and is retried) after this.
+    pub read_timeout: Duration,
+    /// Proxy URL for all requests, e.g. `http://proxy.corp:3128`. Without one,
+    /// the usual `HTTPS_PROXY`/`ALL_PROXY` environment variables apply.
+    pub proxy: Option<String>,
+    /// Base URL that replaces the scheme and host of every file URL, e.g. a
+    /// Hugging Face mirror (`https://hf-mirror.com`) or a local server.
+    pub mirror: Option<String>,
+    /// How many files of a model are downloaded at the same time.
+    pub parallel_files: usize,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            cancel: Arc::new(AtomicBool::new(false)),
+            keep_partial_on_cancel: true,
+            retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            connect_timeout: Duration::from_secs(15),
+            read_timeout: Duration::from_secs(60),
+            proxy: None,
+            mirror: None,
+            parallel_files: 3,
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Options from the user config. Without a configured mirror, `HF_ENDPOINT`
+    /// is used if set.
+    pub fn from_config(config: &DiktoConfig) -> Self {
+        let mirror = config.download_mirror.clone().or_else(|| {
+            std::env::var(HF_ENDPOINT_ENV)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+        });
+        Self {
+            keep_partial_on_cancel: config.keep_partial_downloads,
+            retries: config.download_retries,
+            connect_timeout: Duration::from_secs(config.download_connect_timeout_secs.into()),
+            read_timeout: Duration::from_secs(config.download_read_timeout_secs.into()),
+            proxy: config.download_proxy.clone(),
+            mirror,
+            parallel_files: config.download_parallel_files as usize,
+            ..Self::default()
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Acquire)
+    }
+
+    /// Resolves once the download is cancelled.
+    async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Delay before retry number `attempt` (0-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RETRY_BACKOFF)
+    }
+
+    /// The URL to fetch for `url`, after applying the mirror.
+    fn url_for(&self, url: &str) -> String {
+        match &self.mirror {
+            Some(mirror) => mirror_url(url, mirror),
+            None => url.to_string(),
+        }
+    }
+
+    pub(crate) fn build_client(&self) -> Result<reqwest::Client, ModelError> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .read_timeout(self.read_timeout);
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| ModelError::DownloadFailed(format!("Invalid proxy '{proxy}': {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Rewrite `url` to be served from `mirror`: its scheme and host are replaced
+/// by the mirror base URL (which may include a path prefix), keeping the path.
+/// `https://huggingface.co/a/b.bin` with mirror `https://hf-mirror.com` gives
+/// `https://hf-mirror.com/a/b.bin`.
+pub fn mirror_url(url: &str, mirror: &str) -> String {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or("/"),
+        None => url,
+    };
+    format!("{}{path}", mirror.trim_end_matches('/'))
+}
+
+/// Whether a failed download attempt is worth retrying.
+fn is_retryable(err: &ModelError) -> bool {
+    match err {
+        ModelError::Http(e) => !e.is_builder() && !e.is_redirect(),
+        ModelError::HttpStatus(status, _) => *status == 408 || *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
+
+/// Progress of one file of a model download.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileProgress {
+    pub filename: &'static str,
+    /// Bytes of this file on disk so far.
+    pub downloaded: u64,
+    /// Size of this file: from the server once known, else the registry estimate.
+    pub total: u64,
+}
+
 /// Download a model with progress callback.
 /// `on_progress` receives (bytes_downloaded, total_bytes).
+/// Interrupted downloads leave a `<file>.downloading` partial file that the
+/// next call resumes with an HTTP Range request.
 pub async fn download_model<F>(name: &str, on_progress: F) -> Result<PathBuf, ModelError>
 where
     F: Fn(u64, u64) + Send + 'static,
 {
-    let model = find_model(name).ok_or_else(|| {
-        let available = MODELS.iter().map(|m| m.name).collect::<Vec<_>>().join(", ");
-        ModelError::NotFound(name.to_string(), available)
-    })?;
+    download_model_with_options(name, &DownloadOptions::default(), on_progress, |_| {}).await
+}
 
+/// Like `download_model`, with cancellation and other options.
+/// `on_file_progress` additionally reports the progress of each file.
+pub async fn download_model_with_options<F, G>(
+    name: &str,
+    options: &DownloadOptions,
+    on_progress: F,
+    on_file_progress: G,
+) -> Result<PathBuf, ModelError>
+where
+    F: Fn(u64, u64) + Send + 'static,
+    G: Fn(&FileProgress) + Send + 'static,
+{
+    let model = find_model(name).ok_or_else(|| not_found(name))?;
     let dir = models_dir().join(name);
-    std::fs::create_dir_all(&dir)?;
+    adopt_install(name, &dir)?;
+    download_model_files(model, &dir, options, on_progress, on_file_progress).await?;
 
-    // Calculate total size and already-downloaded bytes
-    let total_bytes: u64 = model
-        .files
-        .iter()
-        .map(|f| f.size_mb as u64 * 1024 * 1024)
-        .sum();
-    let mut cumulative_downloaded: u64 = 0;
+    info!(
+        "All files for model '{}' downloaded to {}",
+        name,
+        dir.display()
+    );
+    Ok(dir)
+}
 
-    for file in model.files {
-        let dest = dir.join(file.filename);
-
-        if dest.exists() {
-            // Count existing file size towards progress
-            let existing_size = std::fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
-            cumulative_downloaded += existing_size;
-            on_progress(cumulative_downloaded, total_bytes);
-            info!("File {} already exists, skipping", file.filename);
-            continue;
+/// Give an install from an older version, which has files but no install
+/// record, a record of the definition it was installed from, so catalog
+/// updates to it are downloaded.
+fn adopt_install(name: &str, dir: &Path) -> Result<(), ModelError> {
+    let Some(base) = base_model(name) else {
+        return Ok(());
+    };
+    let has_files = base.files.iter().any(|f| dir.join(f.filename).exists());
+    if has_files && read_install_record(dir).is_none() {
+        write_install_record(base, dir)?;
+    }
+    Ok(())
+}
+
+/// Download every missing file of `model` into `dir`, resuming partial files.
+/// Files the install record lists with another hash are replaced.
+/// Up to `options.parallel_files` files are fetched at once; `on_progress`
+/// receives the sum over all files and `on_file_progress` each file's own
+/// progress. After a file fails, no further files are started, but those
+/// already running finish.
+pub async fn download_model_files<F, G>(
+    model: &ModelInfo,
+    dir: &Path,
+    options: &DownloadOptions,
+    on_progress: F,
+    on_file_progress: G,
+) -> Result<(), ModelError>
+where
+    F: Fn(u64, u64) + Send + 'static,
+    G: Fn(&FileProgress) + Send + 'static,
+{
+    use futures::StreamExt;
+
+    std::fs::create_dir_all(dir)?;
+    let client = options.build_client()?;
+
+    // Existing files count as complete, unless the install record shows an
+    // older version; the others start from their partial file and the
+    // registry size estimate
+    let installed = read_install_record(dir);
+    let mut pending = Vec::new();
+    let mut files = Vec::with_capacity(model.files.len());
+    for (index, file) in model.files.iter().enumerate() {
+        let existing = std::fs::metadata(dir.join(file.filename))
+            .ok()
+            .filter(|_| !is_outdated(file, installed.as_ref()));
+        let progress = match existing {
+            Some(meta) => {
+                info!("File {} already exists, skipping", file.filename);
+                FileProgress {
+                    filename: file.filename,
+                    downloaded: meta.len(),
+                    total: meta.len(),
+                }
+            }
+            None => {
+                pending.push(index);
+                let partial = dir.join(format!("{}.downloading", file.filename));
+                FileProgress {
+                    filename: file.filename,
+                    downloaded: std::fs::metadata(partial).map(|m| m.len()).unwrap_or(0),
+                    total: file.size_mb as u64 * 1024 * 1024,
+                }
+            }
+        };
+        files.push(progress);
+    }
+    let progress = Mutex::new(files);
+
+    let report = |index: usize, downloaded: u64, total: u64| {
+        let (file, downloaded_sum, total_sum) = {
+            let Ok(mut files) = progress.lock() else {
+                return;
+            };
+            files[index].downloaded = downloaded;
+            files[index].total = total;
+            let downloaded_sum: u64 = files.iter().map(|f| f.downloaded).sum();
+            let total_sum: u64 = files.iter().map(|f| f.total).sum();
+            (files[index].clone(), downloaded_sum, total_sum)
+        };
+        on_file_progress(&file);
+        on_progress(downloaded_sum, total_sum);
+    };
+    if let Ok(files) = progress.lock() {
+        on_progress(
+            files.iter().map(|f| f.downloaded).sum(),
+            files.iter().map(|f| f.total).sum(),
+        );
+    }
+
+    let failed = AtomicBool::new(false);
+    let results: Vec<Result<(), ModelError>> = futures::stream::iter(pending)
+        .map(|index| {
+            let file = &model.files[index];
+            let (client, report, failed) = (&client, &report, &failed);
+            async move {
+                if failed.load(Ordering::Acquire) {
+                    return Ok(());
+                }
+                if options.is_cancelled() {
+                    return Err(ModelError::Cancelled);
+                }
+                let result = download_file(client, file, dir, options, &mut |done, total| {
+                    report(index, done, total)
+                })
+                .await;
+                match result {
+                    Ok(size) => {
+                        report(index, size, size);
+                        info!("Downloaded {}", file.filename);
+                        Ok(())
+                    }
+                    Err(e) => {
+                        failed.store(true, Ordering::Release);
+                        Err(e)
+                    }
+                }
+            }
+        })
+        .buffer_unordered(options.parallel_files.max(1))
+        .collect()
+        .await;
+
+    results.into_iter().find(Result::is_err).unwrap_or(Ok(()))?;
+    write_install_record(model, dir)
+}
+
+/// Download one file to `dir/<filename>` via `<filename>.downloading`.
+/// An existing partial file is resumed with a Range request; if the server
+/// ignores the range, the download restarts from zero. Retryable failures are
+/// retried with exponential backoff, resuming what was received. The partial
+/// file is kept on network errors and deleted when verification fails (or on
+/// cancellation, unless the options keep it).
+/// `on_file_progress` receives the bytes of this file on disk so far and its
+/// expected size. Returns the final file size.
+async fn download_file(
+    client: &reqwest::Client,
+    file: &ModelFile,
+    dir: &Path,
+    options: &DownloadOptions,
+    on_file_progress: &mut dyn FnMut(u64, u64),
+) -> Result<u64, ModelError> {
+    let temp_dest = dir.join(format!("{}.downloading", file.filename));
+    let mut attempt = 0;
+    let result = loop {
+        let result = download_file_inner(client, file, dir, options, on_file_progress).await;
+        match result {
+            Err(e) if attempt < options.retries && is_retryable(&e) => {
+                let delay = options.backoff(attempt);
+                attempt += 1;
+                warn!(
+                    "Download of {} failed: {e}; retrying in {:.1}s ({attempt}/{})",
+                    file.filename,
+                    delay.as_secs_f32(),
+                    options.retries
+                );
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = options.cancelled() => break Err(ModelError::Cancelled),
+                }
+            }
+            result => break result,
         }
+    };
+    if matches!(result, Err(ModelError::Cancelled)) {
+        if options.keep_partial_on_cancel {
+            info!(
+                "Download of {} cancelled, keeping partial file",
+                file.filename
+            );
+        } else {
+            info!(
+                "Download of {} cancelled, deleting partial file",
+                file.filename
+            );
+            let _ = tokio::fs::remove_file(&temp_dest).await;
+        }
+    }
+    result
+}
 
+async fn download_file_inner(
+    client: &reqwest::Client,
+    file: &ModelFile,
+    dir: &Path,
+    options: &DownloadOptions,
+    on_file_progress: &mut dyn FnMut(u64, u64),
+) -> Result<u64, ModelError> {
+    use futures::StreamExt;
+    use reqwest::header::{CONTENT_RANGE, RANGE};
+    use reqwest::StatusCode;
+    use tokio::io::AsyncWriteExt;
+
+    let dest = dir.join(file.filename);
+    let temp_dest = dir.join(format!("{}.downloading", file.filename));
+
+    let mut offset = tokio::fs::metadata(&temp_dest)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let url = options.url_for(file.url);
+    let mut request = client.get(&url);
+    if offset > 0 {
+        info!("Resuming {} from {} bytes ({})", file.filename, offset, url);
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    } else {
         info!(
             "Downloading {} ({} MB) from {}",
-            file.filename, file.size_mb, file.url
+            file.filename, file.size_mb, url
         );
+    }
+    let mut response = tokio::select! {
+        response = request.send() => response?,
+        _ = options.cancelled() => return Err(ModelError::Cancelled),
+    };
 
-        let response = reqwest::get(file.url).await?;
+    // Decide whether the response continues the partial file
+    let mut partial_complete = false;
+    if offset > 0 {
+        let status = response.status();
+        let resumes = status == StatusCode::PARTIAL_CONTENT
+            && response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(content_range_start)
+                == Some(offset);
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            // Nothing past the end: the partial file is probably complete
+            partial_complete = true;
+        } else if !resumes {
+            info!(
+                "Server did not resume {} (HTTP {status}), restarting",
+                file.filename
+            );
+            offset = 0;
+            if status == StatusCode::PARTIAL_CONTENT {
+                // The body is the wrong range; ask for the whole file
+                response = tokio::select! {
+                    response = client.get(&url).send() => response?,
+                    _ = options.cancelled() => return Err(ModelError::Cancelled),
+                };
+            }
+        }
+    }
 
+    if partial_complete {
+        on_file_progress(offset, offset);
+    } else {
         if !response.status().is_success() {
-            return Err(ModelError::DownloadFailed(format!(
-                "HTTP {} for {}",
-                response.status(),
-                file.filename
-            )));
+            return Err(ModelError::HttpStatus(
+                response.status().as_u16(),
+                file.filename.to_string(),
+            ));
         }
+        let total = response
+            .content_length()
+            .map(|len| offset + len)
+            .unwrap_or(file.size_mb as u64 * 1024 * 1024);
 
-        let temp_dest = dir.join(format!("{}.downloading", file.filename));
+        let mut out = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(offset > 0)
+            .truncate(offset == 0)
+            .open(&temp_dest)
+            .await?;
+        on_file_progress(offset, total);
 
-        // Use a closure to ensure temp file cleanup on any error
-        let download_result: Result<(), ModelError> = async {
-            use futures::StreamExt;
-            let mut stream = response.bytes_stream();
-            let mut out = tokio::fs::File::create(&temp_dest)
-                .await
-                .map_err(ModelError::Io)?;
-
-            use tokio::io::AsyncWriteExt;
-            while let Some(chunk) = stream.next().await {
+        let mut stream = response.bytes_stream();
+        let streamed: Result<(), ModelError> = async {
+            loop {
+                let chunk = tokio::select! {
+                    chunk = stream.next() => chunk,
+                    _ = options.cancelled() => return Err(ModelError::Cancelled),
+                };
+                let Some(chunk) = chunk else { break };
                 let chunk = chunk?;
-                out.write_all(&chunk).await.map_err(ModelError::Io)?;
-                cumulative_downloaded += chunk.len() as u64;
-                on_progress(cumulative_downloaded, total_bytes);
+                out.write_all(&chunk).await?;
+                offset += chunk.len() as u64;
+                on_file_progress(offset, total);
             }
-            out.flush().await.map_err(ModelError::Io)?;
-            drop(out);
-
-            // Verify SHA-256 hash if provided
-            if !file.sha256.is_empty() {
-                let temp_path = temp_dest.clone();
-                let expected_hash = file.sha256.to_string();
-                let hash_ok = tokio::task::spawn_blocking(move || {
-                    verify_file_sha256(&temp_path, &expected_hash)
-                })
+            Ok(())
+        }
+        .await;
+        // Flush even on error so the partial file holds everything received
+        out.flush().await?;
+        drop(out);
+        streamed?;
+    }
+
+    if options.is_cancelled() {
+        return Err(ModelError::Cancelled);
+    }
+
+    // Verify SHA-256 hash (over the whole file) if provided
+    if let Err(e) = verify_download(file, &temp_dest).await {
+        // A corrupt partial file can't be resumed
+        let _ = tokio::fs::remove_file(&temp_dest).await;
+        return Err(e);
+    }
+
+    tokio::fs::rename(&temp_dest, &dest).await?;
+    Ok(tokio::fs::metadata(&dest).await?.len())
+}
+
+/// Check a finished download against its SHA-256, or its approximate size
+/// when no hash is known.
+async fn verify_download(file: &ModelFile, path: &Path) -> Result<(), ModelError> {
+    if !file.sha256.is_empty() {
+        let temp_path = path.to_path_buf();
+        let expected_hash = file.sha256.to_string();
+        let hash_ok =
+            tokio::task::spawn_blocking(move || verify_file_sha256(&temp_path, &expected_hash))
                 .await
                 .map_err(|e| ModelError::DownloadFailed(format!("Hash task failed: {e}")))?;
 
-                if !hash_ok {
-                    return Err(ModelError::DownloadFailed(format!(
-                        "SHA-256 mismatch for {}",
-                        file.filename
-                    )));
-                }
-                info!("SHA-256 verified for {}", file.filename);
-            } else if file.size_mb > 0 {
-                // Fallback: verify file size (within 10% of expected)
-                let actual_size = tokio::fs::metadata(&temp_dest)
-                    .await
-                    .map_err(ModelError::Io)?
-                    .len();
-                let expected_size = file.size_mb as u64 * 1024 * 1024;
-                let tolerance = expected_size / 10;
-                if actual_size < expected_size.saturating_sub(tolerance) {
-                    return Err(ModelError::DownloadFailed(format!(
-                        "Size mismatch for {}: expected ~{} MB, got {} bytes",
-                        file.filename, file.size_mb, actual_size
-                    )));
-                }
+        if !hash_ok {
+            return Err(ModelError::DownloadFailed(format!(
+                "SHA-256 mismatch for {}",
+                file.filename
+            )));
+        }
+        info!("SHA-256 verified for {}", file.filename);
+    } else if file.size_mb > 0 {
+        // Fallback: verify file size (within 10% of expected)
+        let actual_size = tokio::fs::metadata(path).await?.len();
+        let expected_size = file.size_mb as u64 * 1024 * 1024;
+        let tolerance = expected_size / 10;
+        if actual_size < expected_size.saturating_sub(tolerance) {
+            return Err(ModelError::DownloadFailed(format!(
+                "Size mismatch for {}: expected ~{} MB, got {} bytes",
+                file.filename, file.size_mb, actual_size
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// First byte position of a `Content-Range: bytes <start>-<end>/<total>` header.
+fn content_range_start(value: &str) -> Option<u64> {
+    value
+        .strip_prefix("bytes ")?
+        .split('-')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// How `import_model` places files into the models directory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportMode {
+    /// Copy the files (the source can be removed afterwards).
+    Copy,
+    /// Symlink to the files (saves disk space; the source must stay in place).
+    Symlink,
+}
+
+/// Install an already-downloaded model from `source` without downloading.
+/// `source` is either a directory containing the model's files or, for
+/// single-file models (ggml Whisper), the file itself.
+pub fn import_model(name: &str, source: &Path, mode: ImportMode) -> Result<PathBuf, ModelError> {
+    let model = find_model(name).ok_or_else(|| not_found(name))?;
+    let dir = models_dir().join(name);
+    import_model_files(model, source, &dir, mode)?;
+    info!("Imported model '{}' into {}", name, dir.display());
+    Ok(dir)
+}
+
+/// Validate `model`'s files at `source` (existence, SHA-256 when known) and
+/// copy or symlink them into `dest_dir`. Nothing is installed unless every
+/// file is valid.
+pub fn import_model_files(
+    model: &ModelInfo,
+    source: &Path,
+    dest_dir: &Path,
+    mode: ImportMode,
+) -> Result<(), ModelError> {
+    // Map each model file to its source path
+    let sources: Vec<(&ModelFile, PathBuf)> = if source.is_dir() {
+        model
+            .files
+            .iter()
+            .map(|f| (f, source.join(f.filename)))
+            .collect()
+    } else if source.is_file() {
+        match model.files {
+            [file] => vec![(file, source.to_path_buf())],
+            _ => {
+                return Err(ModelError::ImportFailed(format!(
+                    "'{}' has {} files; pass the directory containing them",
+                    model.name,
+                    model.files.len()
+                )))
             }
+        }
+    } else {
+        return Err(ModelError::ImportFailed(format!(
+            "{} does not exist",
+            source.display()
+        )));
+    };
 
-            tokio::fs::rename(&temp_dest, &dest)
-                .await
-                .map_err(ModelError::Io)?;
+    for (file, path) in &sources {
+        if !path.is_file() {
+            return Err(ModelError::ImportFailed(format!(
+                "missing {} (expected at {})",
+                file.filename,
+                path.display()
+            )));
+        }
+        if !file.sha256.is_empty() {
+            if !verify_file_sha256(path, file.sha256) {
+                return Err(ModelError::ImportFailed(format!(
+                    "SHA-256 mismatch for {}",
+                    path.display()
+                )));
+            }
+            info!("SHA-256 verified for {}", file.filename);
+        }
+    }
 
-            Ok(())
+    std::fs::create_dir_all(dest_dir)?;
+    for (file, path) in &sources {
+        let dest = dest_dir.join(file.filename);
+        let source = std::fs::canonicalize(path)?;
+        // Importing from the model's own directory: the file is already in
+        // place, and replacing it would destroy the source
+        let is_link = dest
+            .symlink_metadata()
+            .is_ok_and(|m| m.file_type().is_symlink());
+        if !is_link && std::fs::canonicalize(&dest).is_ok_and(|d| d == source) {
+            info!("{} is already in place", file.filename);
+            continue;
         }
-        .await;
 
-        // Clean up temp file on any error
-        if let Err(e) = download_result {
-            let _ = tokio::fs::remove_file(&temp_dest).await;
+        // Create the file under a temporary name and rename it over `dest`,
+        // so an interrupted import never looks installed and the old file
+        // stays until the new one is complete
+        let temp_dest = dest_dir.join(format!("{}.importing", file.filename));
+        if temp_dest.symlink_metadata().is_ok() {
+            std::fs::remove_file(&temp_dest)?;
+        }
+        let created = match mode {
+            ImportMode::Copy => std::fs::copy(&source, &temp_dest)
+                .map(drop)
+                .map_err(ModelError::Io),
+            ImportMode::Symlink => symlink_file(&source, &temp_dest),
+        };
+        if let Err(e) = created {
+            let _ = std::fs::remove_file(&temp_dest);
             return Err(e);
         }
-
-        info!("Downloaded {}", file.filename);
+        std::fs::rename(&temp_dest, &dest)?;
     }
+    write_install_record(model, dest_dir)
+}
 
-    info!(
-        "All files for model '{}' downloaded to {}",
-        name,
-        dir.display()
-    );
-    Ok(dir)
+#[cfg(unix)]
+fn symlink_file(target: &Path, link: &Path) -> Result<(), ModelError> {
+    Ok(std::os::unix::fs::symlink(target, link)?)
+}
+
+#[cfg(windows)]
+fn symlink_file(target: &Path, link: &Path) -> Result<(), ModelError> {
+    Ok(std::os::windows::fs::symlink_file(target, link)?)
 }
 
 /// Verify the SHA-256 hash of a file.
@@ -326,19 +1150,289 @@ pub fn verify_file_sha256(path: &std::path::Path, expected_hex: &str) -> bool {
     actual == expected_hex
 }
 
+/// Result of checking an installed model's files against the registry.
+#[derive(Debug, Clone, Default, PartialEq, uniffi::Record)]
+pub struct VerifyReport {
+    /// Model files that are not on disk.
+    pub missing: Vec<String>,
+    /// Model files whose SHA-256 (or, without a known hash, size) is wrong.
+    pub corrupt: Vec<String>,
+    /// Files in the model directory that don't belong to the model, such as
+    /// partial downloads.
+    pub extra: Vec<String>,
+    /// Model files without a known hash, so only their size was checked.
+    pub unverified: Vec<String>,
+}
+
+impl VerifyReport {
+    /// True if every model file is present and intact.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Re-hash the installed files of model `name`. See `verify_model_files`.
+pub fn verify_model(name: &str) -> Result<VerifyReport, ModelError> {
+    let model = find_model(name).ok_or_else(|| not_found(name))?;
+    verify_model_files(model, &models_dir().join(name))
+}
+
+/// Check every file of `model` in `dir` against its SHA-256, or its size when
+/// no hash is known, and list files in `dir` that aren't part of the model.
+/// Reads all model files, which takes a while for large models.
+pub fn verify_model_files(model: &ModelInfo, dir: &Path) -> Result<VerifyReport, ModelError> {
+    let mut report = VerifyReport::default();
+    for file in model.files {
+        let path = dir.join(file.filename);
+        let Ok(meta) = std::fs::metadata(&path) else {
+            report.missing.push(file.filename.to_string());
+            continue;
+        };
+        if !file.sha256.is_empty() {
+            if !verify_file_sha256(&path, file.sha256) {
+                report.corrupt.push(file.filename.to_string());
+            }
+        } else {
+            // Same tolerance as after a download
+            let expected = file.size_mb as u64 * 1024 * 1024;
+            if meta.len() < expected.saturating_sub(expected / 10) {
+                report.corrupt.push(file.filename.to_string());
+            } else {
+                report.unverified.push(file.filename.to_string());
+            }
+        }
+    }
+
+    match std::fs::read_dir(dir) {
+        Ok(entries) => {
+            for entry in entries {
+                let name = entry?.file_name().to_string_lossy().into_owned();
+                if name != INSTALL_RECORD && !model.files.iter().any(|f| f.filename == name) {
+                    report.extra.push(name);
+                }
+            }
+            report.extra.sort();
+        }
+        // Nothing installed: every file is missing
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    Ok(report)
+}
+
+/// Verify model `name` and re-download its missing and corrupt files.
+/// Returns the report after the repair. See `repair_model_files`.
+pub async fn repair_model<F>(
+    name: &str,
+    options: &DownloadOptions,
+    on_progress: F,
+) -> Result<VerifyReport, ModelError>
+where
+    F: Fn(u64, u64) + Send + 'static,
+{
+    let model = find_model(name).ok_or_else(|| not_found(name))?;
+    repair_model_files(model, &models_dir().join(name), options, on_progress).await
+}
+
+/// Verify `model` in `dir`, delete corrupt files and download the missing
+/// ones; intact files are kept. Extra files are left alone. Returns the
+/// report of a fresh verification after the repair.
+pub async fn repair_model_files<F>(
+    model: &ModelInfo,
+    dir: &Path,
+    options: &DownloadOptions,
+    on_progress: F,
+) -> Result<VerifyReport, ModelError>
+where
+    F: Fn(u64, u64) + Send + 'static,
+{
+    let report = verify_in_background(model, dir).await?;
+    if report.is_ok() {
+        return Ok(report);
+    }
+
+    for filename in &report.corrupt {
+        info!("Removing corrupt file {filename}");
+        tokio::fs::remove_file(dir.join(filename)).await?;
+    }
+    download_model_files(model, dir, options, on_progress, |_| {}).await?;
+    verify_in_background(model, dir).await
+}
+
+/// `verify_model_files` on the blocking thread pool.
+async fn verify_in_background(model: &ModelInfo, dir: &Path) -> Result<VerifyReport, ModelError> {
+    let model = model.clone();
+    let dir = dir.to_path_buf();
+    tokio::task::spawn_blocking(move || verify_model_files(&model, &dir))
+        .await
+        .map_err(|e| ModelError::DownloadFailed(format!("Verify task failed: {e}")))?
+}
+
+/// File in a model directory recording the SHA-256 of each installed file.
+/// Written when a download or import completes, so catalog updates can be
+/// detected without re-hashing.
+pub const INSTALL_RECORD: &str = ".installed.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstallRecord {
+    /// Filename to SHA-256 (empty when the hash isn't known).
+    files: BTreeMap<String, String>,
+}
+
+/// Files installed in `dir` with their hashes: the install record, or
+/// without one the files of `fallback`.
+fn installed_files(dir: &Path, fallback: Option<&ModelInfo>) -> Option<BTreeMap<String, String>> {
+    read_install_record(dir).or_else(|| {
+        fallback.map(|m| {
+            m.files
+                .iter()
+                .map(|f| (f.filename.to_string(), f.sha256.to_string()))
+                .collect()
+        })
+    })
+}
+
+/// Hashes of the files installed in `dir`, if it has an install record.
+pub fn read_install_record(dir: &Path) -> Option<BTreeMap<String, String>> {
+    let json = std::fs::read_to_string(dir.join(INSTALL_RECORD)).ok()?;
+    match serde_json::from_str::<InstallRecord>(&json) {
+        Ok(record) => Some(record.files),
+        Err(e) => {
+            warn!("Ignoring invalid install record in {}: {e}", dir.display());
+            None
+        }
+    }
+}
+
+/// Record `model`'s files as installed in `dir`.
+fn write_install_record(model: &ModelInfo, dir: &Path) -> Result<(), ModelError> {
+    let record = InstallRecord {
+        files: model
+            .files
+            .iter()
+            .map(|f| (f.filename.to_string(), f.sha256.to_string()))
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&record)
+        .map_err(|e| ModelError::DownloadFailed(format!("Install record: {e}")))?;
+    std::fs::write(dir.join(INSTALL_RECORD), json)?;
+    Ok(())
+}
+
+/// Whether the installed file `file` is an older version than `file`
+/// describes, according to `installed` hashes. Unknown hashes never count
+/// as outdated.
+fn is_outdated(file: &ModelFile, installed: Option<&BTreeMap<String, String>>) -> bool {
+    match installed.and_then(|files| files.get(file.filename)) {
+        Some(hash) => !hash.is_empty() && !file.sha256.is_empty() && hash != file.sha256,
+        None => false,
+    }
+}
+
+/// True if model `name` is installed and its current definition (with
+/// catalog updates) lists files that aren't installed in that version.
+pub fn update_available(name: &str) -> bool {
+    match find_model(name) {
+        Some(model) => update_available_in(model, &models_dir().join(name), base_model(name)),
+        None => false,
+    }
+}
+
+/// Whether `model` has a newer version than the one installed in `dir`.
+/// Installed versions come from the install record; without one (installs by
+/// older versions), the files are assumed to match `fallback`, the definition
+/// they were installed from. Nothing installed means no update.
+pub fn update_available_in(model: &ModelInfo, dir: &Path, fallback: Option<&ModelInfo>) -> bool {
+    let Some(installed) = installed_files(dir, fallback) else {
+        return false;
+    };
+    if !installed.keys().any(|f| dir.join(f).exists()) {
+        return false;
+    }
+    // Files new in this version, or installed with another hash
+    model
+        .files
+        .iter()
+        .any(|f| !installed.contains_key(f.filename) || is_outdated(f, Some(&installed)))
+}
+
+/// Disk usage of a model directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskUsage {
+    /// Bytes of all files in the directory, partial files included.
+    pub total_bytes: u64,
+    /// Leftover `.downloading`/`.importing` files of interrupted downloads or imports.
+    pub partial_files: Vec<String>,
+    /// Bytes of the partial files.
+    pub partial_bytes: u64,
+    /// Bytes of files that are symlinks (`--link` imports). Deleting the
+    /// model removes only the links, so these are not freed.
+    pub linked_bytes: u64,
+}
+
+/// Disk usage of model `name`'s directory. See `dir_disk_usage`.
+pub fn model_disk_usage(name: &str) -> Result<DiskUsage, ModelError> {
+    find_model(name).ok_or_else(|| not_found(name))?;
+    dir_disk_usage(&models_dir().join(name))
+}
+
+/// Sum the sizes of the files in `dir` (symlinks count with their target's
+/// size) and list partial files. A missing directory uses no space.
+pub fn dir_disk_usage(dir: &Path) -> Result<DiskUsage, ModelError> {
+    let mut usage = DiskUsage::default();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(usage),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        // Dangling symlinks take no space
+        let Ok(meta) = std::fs::metadata(entry.path()) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        usage.total_bytes += meta.len();
+        if entry.file_type()?.is_symlink() {
+            usage.linked_bytes += meta.len();
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.ends_with(".downloading") || name.ends_with(".importing") {
+            usage.partial_bytes += meta.len();
+            usage.partial_files.push(name);
+        }
+    }
+    usage.partial_files.sort();
+    Ok(usage)
+}
+
 /// Delete a downloaded model (removes the entire model directory).
 pub fn delete_model(name: &str) -> Result<(), ModelError> {
     let Some(_) = find_model(name) else {
-        let available = MODELS.iter().map(|m| m.name).collect::<Vec<_>>().join(", ");
-        return Err(ModelError::NotFound(name.to_string(), available));
+        return Err(not_found(name));
     };
 
-    let dir = models_dir().join(name);
-    if dir.exists() {
-        std::fs::remove_dir_all(&dir)?;
-        info!("Deleted model {} at {}", name, dir.display());
-    } else {
-        warn!("Model {} not found at {}", name, dir.display());
+    let dir = models_dir();
+    if !delete_model_dir(&dir, name)? {
+        warn!("Model {} not found at {}", name, dir.join(name).display());
     }
     Ok(())
 }
+
+/// Delete directory `name` under `models_dir`, whether or not `name` is a
+/// known model: `dikto models du` also lists directories of models that are
+/// no longer in the registry. Returns false if there is no such directory.
+pub fn delete_model_dir(models_dir: &Path, name: &str) -> Result<bool, ModelError> {
+    if !is_plain_name(name) {
+        return Err(not_found(name));
+    }
+    let dir = models_dir.join(name);
+    if !dir.symlink_metadata().is_ok_and(|m| m.is_dir()) {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir)?;
+    info!("Deleted model {} at {}", name, dir.display());
+    Ok(true)
+}
@@ -0,0 +1,262 @@
+use serde::Serialize;
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::transcribe::{TranscriptSegment, WordTimestamp};
+
+/// Subtitle cues never hold more than this many lines.
+const MAX_LINES_PER_CUE: usize = 2;
+
+/// Output format for a finished transcription.
+#[derive(Debug, Clone, Copy, PartialEq, uniffi::Enum)]
+pub enum OutputFormat {
+    /// Plain text, segments joined by spaces.
+    Text,
+    /// SubRip subtitles.
+    Srt,
+    /// WebVTT subtitles.
+    Vtt,
+    /// JSON document with segments, words, language and model.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "txt" => Ok(OutputFormat::Text),
+            "srt" => Ok(OutputFormat::Srt),
+            "vtt" | "webvtt" => Ok(OutputFormat::Vtt),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!(
+                "Unknown output format '{other}' (expected text, srt, vtt or json)"
+            )),
+        }
+    }
+}
+
+/// Layout options for subtitle cues.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SubtitleOptions {
+    /// Maximum characters per subtitle line.
+    pub max_line_length: u32,
+    /// Maximum duration of a single cue in seconds.
+    pub max_cue_duration_secs: f64,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_length: 42,
+            max_cue_duration_secs: 7.0,
+        }
+    }
+}
+
+/// A single subtitle cue. `text` may contain line breaks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Split segments into subtitle cues that respect the line length and cue
+/// duration limits. Word timings are used when available; otherwise a long
+/// segment's time span is divided proportionally to word count.
+pub fn build_cues(segments: &[TranscriptSegment], options: &SubtitleOptions) -> Vec<Cue> {
+    let max_len = options.max_line_length.max(1) as usize;
+    let max_dur = options.max_cue_duration_secs.max(0.1);
+
+    let mut cues = Vec::new();
+    for segment in segments {
+        let words = if segment.words.is_empty() {
+            interpolate_words(segment)
+        } else {
+            segment.words.clone()
+        };
+
+        let mut current: Vec<WordTimestamp> = Vec::new();
+        for word in words {
+            if let Some(first) = current.first() {
+                let too_long = word.end - first.start > max_dur;
+                let candidate = join_words(current.iter().chain(std::iter::once(&word)));
+                let too_many_lines = wrap_text(&candidate, max_len).len() > MAX_LINES_PER_CUE;
+                if too_long || too_many_lines {
+                    cues.push(cue_from_words(&current, max_len));
+                    current.clear();
+                }
+            }
+            current.push(word);
+        }
+        if !current.is_empty() {
+            cues.push(cue_from_words(&current, max_len));
+        }
+    }
+    cues
+}
+
+/// Give each word of an untimed segment an equal share of the segment's span.
+fn interpolate_words(segment: &TranscriptSegment) -> Vec<WordTimestamp> {
+    let texts: Vec<&str> = segment.text.split_whitespace().collect();
+    let step = (segment.end - segment.start).max(0.0) / texts.len().max(1) as f64;
+    texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| WordTimestamp {
+            text: text.to_string(),
+            start: segment.start + step * i as f64,
+            end: segment.start + step * (i + 1) as f64,
+        })
+        .collect()
+}
+
+fn join_words<'a>(words: impl Iterator<Item = &'a WordTimestamp>) -> String {
+    words.map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ")
+}
+
+fn cue_from_words(words: &[WordTimestamp], max_len: usize) -> Cue {
+    Cue {
+        start: words[0].start,
+        end: words[words.len() - 1].end,
+        text: wrap_text(&join_words(words.iter()), max_len).join("\n"),
+    }
+}
+
+/// Greedily wrap text into lines of at most `max_len` characters.
+/// A single word longer than `max_len` gets a line of its own.
+pub fn wrap_text(text: &str, max_len: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_len {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Format seconds as `HH:MM:SS<sep>mmm` (`,` for SRT, `.` for WebVTT).
+pub fn format_timestamp(secs: f64, millis_separator: char) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    let hours = total_ms / 3_600_000;
+    let minutes = total_ms / 60_000 % 60;
+    let seconds = total_ms / 1000 % 60;
+    let millis = total_ms % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{millis_separator}{millis:03}")
+}
+
+/// Render segments as SubRip (SRT) subtitles.
+pub fn to_srt(segments: &[TranscriptSegment], options: &SubtitleOptions) -> String {
+    let mut out = String::new();
+    for (i, cue) in build_cues(segments, options).iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ','),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Render segments as WebVTT subtitles.
+pub fn to_vtt(segments: &[TranscriptSegment], options: &SubtitleOptions) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in build_cues(segments, options) {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Join segment texts into plain text.
+pub fn to_text(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Serialize)]
+struct JsonTranscript<'a> {
+    text: String,
+    language: &'a str,
+    model: &'a str,
+    segments: Vec<JsonSegment<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSegment<'a> {
+    start: f64,
+    end: f64,
+    text: &'a str,
+    words: Vec<JsonWord<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonWord<'a> {
+    start: f64,
+    end: f64,
+    text: &'a str,
+}
+
+/// Render segments as a pretty-printed JSON document.
+pub fn to_json(segments: &[TranscriptSegment], language: &str, model_name: &str) -> String {
+    let doc = JsonTranscript {
+        text: to_text(segments),
+        language,
+        model: model_name,
+        segments: segments
+            .iter()
+            .map(|s| JsonSegment {
+                start: s.start,
+                end: s.end,
+                text: &s.text,
+                words: s
+                    .words
+                    .iter()
+                    .map(|w| JsonWord {
+                        start: w.start,
+                        end: w.end,
+                        text: &w.text,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&doc).unwrap_or_else(|e| {
+        warn!("Failed to serialize transcript: {e}");
+        String::new()
+    })
+}
+
+/// Render segments in the given format.
+pub fn format_transcript(
+    segments: &[TranscriptSegment],
+    format: OutputFormat,
+    language: &str,
+    model_name: &str,
+    options: &SubtitleOptions,
+) -> String {
+    match format {
+        OutputFormat::Text => to_text(segments),
+        OutputFormat::Srt => to_srt(segments, options),
+        OutputFormat::Vtt => to_vtt(segments, options),
+        OutputFormat::Json => to_json(segments, language, model_name),
+    }
+}
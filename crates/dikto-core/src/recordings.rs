@@ -0,0 +1,126 @@
+use std::fs::{File, OpenOptions};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+// Session recordings are the exact 16kHz mono audio a session passed to the
+// model, saved as 32-bit float WAV so a wrong transcription can be reproduced
+// with `dikto transcribe`. They live in the data dir and are pruned by count
+// and age after each save.
+
+/// File name prefix of session recordings. Only such files are ever pruned.
+pub const RECORDING_PREFIX: &str = "session-";
+
+/// Write `samples` (16kHz mono) to a new `session-<unix time>.wav` in `dir`,
+/// creating the directory if needed. Returns the path of the file.
+pub fn save_recording(dir: &Path, samples: &[f32]) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let (path, file) = create_unique(dir)?;
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let written = (|| {
+        let mut writer = hound::WavWriter::new(BufWriter::new(file), spec)?;
+        for &sample in samples {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()
+    })();
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&path);
+        return Err(std::io::Error::other(e));
+    }
+
+    debug!(
+        "Saved {:.1}s of session audio to {}",
+        samples.len() as f32 / 16000.0,
+        path.display()
+    );
+    Ok(path)
+}
+
+/// Create a recording file named after the current time, with a numeric
+/// suffix if another session saved one in the same millisecond.
+fn create_unique(dir: &Path) -> std::io::Result<(PathBuf, File)> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let stem = format!(
+        "{RECORDING_PREFIX}{}-{:03}",
+        now.as_secs(),
+        now.subsec_millis()
+    );
+    for attempt in 0u32.. {
+        let name = if attempt == 0 {
+            format!("{stem}.wav")
+        } else {
+            format!("{stem}-{attempt}.wav")
+        };
+        let path = dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("ran out of recording file names")
+}
+
+/// Session recordings in `dir` with their modification times, oldest first.
+pub fn list_recordings(dir: &Path) -> std::io::Result<Vec<(PathBuf, SystemTime)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut recordings = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with(RECORDING_PREFIX) || !name.ends_with(".wav") {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            recordings.push((entry.path(), metadata.modified()?));
+        }
+    }
+    recordings.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(recordings)
+}
+
+/// Delete session recordings in `dir` beyond the newest `max_files`, and
+/// those older than `max_age_days` at `now`. 0 disables either limit.
+/// Returns the number of deleted files.
+pub fn prune_recordings(
+    dir: &Path,
+    max_files: u32,
+    max_age_days: u32,
+    now: SystemTime,
+) -> std::io::Result<usize> {
+    let recordings = list_recordings(dir)?;
+    let excess = match max_files {
+        0 => 0,
+        max => recordings.len().saturating_sub(max as usize),
+    };
+    let max_age = Duration::from_secs(max_age_days as u64 * 24 * 60 * 60);
+
+    let mut deleted = 0;
+    for (index, (path, modified)) in recordings.iter().enumerate() {
+        let too_old =
+            max_age_days > 0 && now.duration_since(*modified).is_ok_and(|age| age > max_age);
+        if index < excess || too_old {
+            match std::fs::remove_file(path) {
+                Ok(()) => deleted += 1,
+                Err(e) => warn!("Failed to delete old recording {}: {e}", path.display()),
+            }
+        }
+    }
+    Ok(deleted)
+}
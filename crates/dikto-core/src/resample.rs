@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+
+/// Trade-off between resampling quality and CPU cost.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, uniffi::Enum)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    /// Short filter with a wide transition band; for slow machines.
+    Fast,
+    /// Rejects aliasing by about 90 dB; flat passband up to ~6 kHz at 16 kHz.
+    #[default]
+    Balanced,
+    /// Longest filter and narrowest transition band.
+    Best,
+}
+
+impl ResampleQuality {
+    /// Filter design: (zero crossings on each side of the kernel, Kaiser
+    /// window beta, cutoff as a fraction of the output Nyquist frequency).
+    fn filter_params(self) -> (usize, f64, f64) {
+        match self {
+            Self::Fast => (8, 6.0, 0.8),
+            Self::Balanced => (24, 9.0, 0.88),
+            Self::Best => (48, 11.0, 0.93),
+        }
+    }
+}
+
+/// Largest number of precomputed filter phases. Rate pairs needing more
+/// (e.g. 44101 Hz to 16 kHz) round positions down to a multiple of
+/// 1/MAX_PHASES of an input sample.
+const MAX_PHASES: usize = 1024;
+
+/// Streaming band-limited resampler for mono audio: a polyphase
+/// windowed-sinc (Kaiser) filter for the rational ratio `to_rate / from_rate`.
+///
+/// The low-pass cutoff sits below the Nyquist frequency of the lower of the
+/// two rates, so content above it is removed instead of folding back into
+/// the speech band. Input can be pushed in blocks of any size; the output is
+/// the same as resampling the concatenated input at once.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    /// Interpolation factor L (`to_rate / gcd`).
+    up: u64,
+    /// Decimation factor M (`from_rate / gcd`).
+    down: u64,
+    /// Filter coefficients, `taps` per phase; phase p is for the fractional
+    /// input position `p / phases`.
+    coeffs: Vec<f32>,
+    phases: usize,
+    taps: usize,
+    /// Input samples not yet consumed, starting `taps / 2 - 1` samples
+    /// before the position of the next output sample.
+    history: Vec<f32>,
+    /// Position of the next output sample in `history` (integer part).
+    pos: usize,
+    /// Fractional part of that position, in units of 1/`up`.
+    phase: u64,
+    /// True if both rates are equal: samples are passed through.
+    passthrough: bool,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32, quality: ResampleQuality) -> Self {
+        let from_rate = from_rate.max(1) as u64;
+        let to_rate = to_rate.max(1) as u64;
+        let g = gcd(from_rate, to_rate);
+        let (up, down) = (to_rate / g, from_rate / g);
+
+        let (zero_crossings, beta, rolloff) = quality.filter_params();
+        // Cutoff relative to the input Nyquist frequency
+        let cutoff = (up as f64 / down as f64).min(1.0) * rolloff;
+        // The kernel spans `zero_crossings` lobes of the sinc on each side,
+        // which is wider in input samples the lower the cutoff
+        let half = (zero_crossings as f64 / cutoff).ceil() as usize;
+        let taps = 2 * half;
+        let phases = (up as usize).min(MAX_PHASES);
+
+        let mut coeffs = Vec::with_capacity(phases * taps);
+        for p in 0..phases {
+            let frac = p as f64 / phases as f64;
+            let start = coeffs.len();
+            for k in 0..taps {
+                // Distance from the output position to input sample k
+                let t = frac + (half - 1) as f64 - k as f64;
+                coeffs.push(kernel(t, cutoff, half as f64, beta));
+            }
+            // Normalize each phase to unity DC gain
+            let sum: f64 = coeffs[start..].iter().sum();
+            for c in &mut coeffs[start..] {
+                *c /= sum;
+            }
+        }
+
+        Self {
+            up,
+            down,
+            coeffs: coeffs.into_iter().map(|c| c as f32).collect(),
+            phases,
+            taps,
+            history: vec![0.0; half - 1],
+            pos: 0,
+            phase: 0,
+            passthrough: up == down,
+        }
+    }
+
+    /// Preallocate for input blocks of up to `max_input` samples, so that
+    /// `process` never allocates for such blocks (if `output` has room for
+    /// `max_output_len(max_input)` more samples).
+    pub fn reserve(&mut self, max_input: usize) {
+        let needed = self.taps + max_input;
+        self.history
+            .reserve(needed.saturating_sub(self.history.len()));
+    }
+
+    /// Most samples `process` can produce for `input_len` input samples.
+    pub fn max_output_len(&self, input_len: usize) -> usize {
+        if self.passthrough {
+            return input_len;
+        }
+        (input_len as u64 * self.up).div_ceil(self.down) as usize + 1
+    }
+
+    /// Resample `input` and append the result to `output`. Output is
+    /// delayed by half the filter length; `finish` flushes it.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if self.passthrough {
+            output.extend_from_slice(input);
+            return;
+        }
+        self.history.extend_from_slice(input);
+
+        while self.pos + self.taps <= self.history.len() {
+            let p = (self.phase * self.phases as u64 / self.up) as usize;
+            let coeffs = &self.coeffs[p * self.taps..(p + 1) * self.taps];
+            let window = &self.history[self.pos..self.pos + self.taps];
+            output.push(window.iter().zip(coeffs).map(|(x, c)| x * c).sum());
+
+            self.phase += self.down;
+            self.pos += (self.phase / self.up) as usize;
+            self.phase %= self.up;
+        }
+
+        // Drop consumed input; keeps its allocation
+        let consumed = self.pos.min(self.history.len());
+        self.history.drain(..consumed);
+        self.pos -= consumed;
+    }
+
+    /// Flush the samples still held back by the filter delay, as if the
+    /// input continued with silence. Resets the resampler for a new stream.
+    pub fn finish(&mut self, output: &mut Vec<f32>) {
+        if !self.passthrough {
+            // Output positions still inside the real input
+            let pending = (self.history.len() + 1).saturating_sub(self.taps / 2 + self.pos);
+            let span = (pending as u64 * self.up).saturating_sub(self.phase);
+            let count = span.div_ceil(self.down) as usize;
+            let start = output.len();
+            let silence = vec![0.0; self.taps];
+            self.process(&silence, output);
+            output.truncate(start + count);
+        }
+        self.reset();
+    }
+
+    /// Forget all buffered input.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.history.resize(self.taps / 2 - 1, 0.0);
+        self.pos = 0;
+        self.phase = 0;
+    }
+}
+
+/// Resample a whole mono signal from `from_rate` to `to_rate`.
+pub fn resample(
+    samples: &[f32],
+    from_rate: u32,
+    to_rate: u32,
+    quality: ResampleQuality,
+) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let mut resampler = Resampler::new(from_rate, to_rate, quality);
+    let expected = samples.len() as u64 * to_rate as u64 / from_rate as u64;
+    let mut output = Vec::with_capacity(expected as usize + 1);
+    resampler.process(samples, &mut output);
+    resampler.finish(&mut output);
+    output
+}
+
+/// Kaiser-windowed sinc low-pass with `cutoff` (fraction of Nyquist),
+/// evaluated `t` input samples from its center.
+fn kernel(t: f64, cutoff: f64, half: f64, beta: f64) -> f64 {
+    let x = t / half;
+    if x.abs() >= 1.0 {
+        return 0.0;
+    }
+    let window = bessel_i0(beta * (1.0 - x * x).sqrt()) / bessel_i0(beta);
+    cutoff * sinc(cutoff * t) * window
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-12 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Zeroth-order modified Bessel function of the first kind (power series).
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x = x / 2.0;
+    for k in 1..50 {
+        term *= (half_x / k as f64) * (half_x / k as f64);
+        sum += term;
+        if term < sum * 1e-16 {
+            break;
+        }
+    }
+    sum
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
@@ -0,0 +1,332 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::audio::{AudioCapture, AudioCaptureConfig, AudioError};
+use crate::decode;
+
+/// Samples handed out per read by non-realtime sources (100ms at 16kHz).
+const READ_CHUNK_SAMPLES: usize = 1600;
+
+/// A source of 16kHz mono f32 audio for the recording pipeline.
+pub trait AudioSource {
+    /// Read the samples that are available right now. May return an empty Vec.
+    fn read_samples(&mut self) -> Vec<f32>;
+
+    /// True once the source will never produce more samples (end of file or stream).
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// Stop producing audio.
+    fn stop(&mut self) {}
+
+    /// Samples lost so far because the source overflowed (a real-time source
+    /// that wasn't read in time). Sources that can't overflow report 0.
+    fn dropped_samples(&self) -> u64 {
+        0
+    }
+}
+
+impl AudioSource for AudioCapture {
+    fn read_samples(&mut self) -> Vec<f32> {
+        AudioCapture::read_samples(self)
+    }
+
+    fn dropped_samples(&self) -> u64 {
+        AudioCapture::dropped_samples(self)
+    }
+
+    fn stop(&mut self) {
+        AudioCapture::stop(self);
+    }
+}
+
+/// Minimum time between two input overflow warnings of a session.
+pub const OVERFLOW_WARNING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Throttles warnings about samples a source dropped. Drops during the
+/// interval after a warning are reported by the next one, even if no more
+/// samples are dropped afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct OverflowWarnings {
+    /// Dropped total reported by the last warning.
+    last_warned: u64,
+    next_due: Option<Instant>,
+}
+
+impl OverflowWarnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The source has dropped `dropped` samples so far. Returns the warning
+    /// to send at `now`, if there are unreported drops and one is due.
+    pub fn check(&mut self, dropped: u64, now: Instant) -> Option<String> {
+        if dropped <= self.last_warned || self.next_due.is_some_and(|due| now < due) {
+            return None;
+        }
+        self.last_warned = dropped;
+        self.next_due = Some(now + OVERFLOW_WARNING_INTERVAL);
+        Some(format!(
+            "Audio input overflow: {dropped} samples ({:.1}s) dropped so far",
+            dropped as f64 / 16000.0
+        ))
+    }
+}
+
+/// In-memory source that hands out a pre-recorded buffer in small chunks.
+pub struct MemorySource {
+    samples: Vec<f32>,
+    pos: usize,
+}
+
+impl MemorySource {
+    /// Create a source from 16kHz mono samples.
+    pub fn new(samples: Vec<f32>) -> Self {
+        Self { samples, pos: 0 }
+    }
+}
+
+impl AudioSource for MemorySource {
+    fn read_samples(&mut self) -> Vec<f32> {
+        let end = (self.pos + READ_CHUNK_SAMPLES).min(self.samples.len());
+        let chunk = self.samples[self.pos..end].to_vec();
+        self.pos = end;
+        chunk
+    }
+
+    fn is_finished(&self) -> bool {
+        self.pos >= self.samples.len()
+    }
+
+    fn stop(&mut self) {
+        self.pos = self.samples.len();
+    }
+}
+
+/// File-backed source. The file is decoded up front to 16kHz mono.
+pub struct FileSource {
+    inner: MemorySource,
+}
+
+impl FileSource {
+    /// Decode an audio file (WAV, FLAC, MP3, OGG) into a source.
+    pub fn open(path: &Path) -> Result<Self, AudioError> {
+        let samples = decode::decode_file(path).map_err(|e| AudioError::Source(e.to_string()))?;
+        debug!("File source {}: {} samples", path.display(), samples.len());
+        Ok(Self {
+            inner: MemorySource::new(samples),
+        })
+    }
+}
+
+impl AudioSource for FileSource {
+    fn read_samples(&mut self) -> Vec<f32> {
+        self.inner.read_samples()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn stop(&mut self) {
+        self.inner.stop();
+    }
+}
+
+/// Raw PCM sample encoding for stream sources.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PcmFormat {
+    /// 32-bit little-endian float.
+    F32Le,
+    /// 16-bit little-endian signed integer.
+    S16Le,
+}
+
+impl PcmFormat {
+    fn bytes_per_sample(self) -> usize {
+        match self {
+            PcmFormat::F32Le => 4,
+            PcmFormat::S16Le => 2,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            PcmFormat::F32Le => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            PcmFormat::S16Le => i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / i16::MAX as f32,
+        }
+    }
+}
+
+/// Incremental PCM decoder that carries incomplete samples over between
+/// byte chunks (reads, network frames) of arbitrary size.
+#[derive(Debug, Clone)]
+pub struct PcmDecoder {
+    format: PcmFormat,
+    pending: Vec<u8>,
+}
+
+impl PcmDecoder {
+    pub fn new(format: PcmFormat) -> Self {
+        Self {
+            format,
+            pending: Vec::with_capacity(format.bytes_per_sample()),
+        }
+    }
+
+    /// Decode all whole samples in `pending + bytes`, keeping any leftover bytes.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<f32> {
+        let sample_bytes = self.format.bytes_per_sample();
+        self.pending.extend_from_slice(bytes);
+        let whole = self.pending.len() - self.pending.len() % sample_bytes;
+        let samples = self.pending[..whole]
+            .chunks_exact(sample_bytes)
+            .map(|b| self.format.decode(b))
+            .collect();
+        self.pending.drain(..whole);
+        samples
+    }
+}
+
+/// Source fed with sample buffers from another thread or task, e.g. audio
+/// received over the network. Finishes once every sender is dropped.
+pub struct ChannelSource {
+    rx: Receiver<Vec<f32>>,
+    finished: bool,
+}
+
+impl ChannelSource {
+    /// Create a source and the sender that feeds it.
+    pub fn new() -> (Sender<Vec<f32>>, Self) {
+        let (tx, rx) = mpsc::channel();
+        (
+            tx,
+            Self {
+                rx,
+                finished: false,
+            },
+        )
+    }
+}
+
+impl AudioSource for ChannelSource {
+    fn read_samples(&mut self) -> Vec<f32> {
+        let mut out = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(samples) => out.extend_from_slice(&samples),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        out
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn stop(&mut self) {
+        self.finished = true;
+    }
+}
+
+/// Source that reads raw 16kHz mono PCM from any reader (e.g. stdin) on a
+/// background thread, so `read_samples` never blocks the pipeline.
+pub struct PcmReaderSource {
+    inner: ChannelSource,
+}
+
+impl PcmReaderSource {
+    /// Start reading PCM from `reader`.
+    pub fn new<R: Read + Send + 'static>(mut reader: R, format: PcmFormat) -> Self {
+        let (tx, inner) = ChannelSource::new();
+        std::thread::spawn(move || {
+            let mut decoder = PcmDecoder::new(format);
+            let mut buf = vec![0u8; 8192];
+            loop {
+                let n = match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        warn!("PCM reader error: {e}");
+                        break;
+                    }
+                };
+                let samples = decoder.push(&buf[..n]);
+                if !samples.is_empty() && tx.send(samples).is_err() {
+                    break;
+                }
+            }
+            debug!("PCM reader reached end of stream");
+        });
+        Self { inner }
+    }
+
+    /// Read PCM from the process's standard input.
+    pub fn stdin(format: PcmFormat) -> Self {
+        Self::new(std::io::stdin(), format)
+    }
+}
+
+impl AudioSource for PcmReaderSource {
+    fn read_samples(&mut self) -> Vec<f32> {
+        self.inner.read_samples()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn stop(&mut self) {
+        self.inner.stop();
+    }
+}
+
+/// Factory for a custom audio source, called on the pipeline thread.
+pub type SourceFactory = Box<dyn FnOnce() -> Result<Box<dyn AudioSource>, AudioError> + Send>;
+
+/// Where a listening session gets its audio from.
+///
+/// Sources are opened on the pipeline thread, because some of them
+/// (like the cpal microphone stream) cannot be moved between threads.
+pub enum AudioInput {
+    /// Live capture from an input device.
+    Microphone(AudioCaptureConfig),
+    /// An audio file, decoded up front.
+    File(PathBuf),
+    /// Raw 16kHz mono PCM on standard input.
+    Stdin(PcmFormat),
+    /// Pre-recorded 16kHz mono samples.
+    Samples(Vec<f32>),
+    /// Any other source.
+    Custom(SourceFactory),
+}
+
+impl Default for AudioInput {
+    fn default() -> Self {
+        AudioInput::Microphone(AudioCaptureConfig::default())
+    }
+}
+
+impl AudioInput {
+    /// Open the input, starting capture or decoding as needed.
+    pub fn open(self) -> Result<Box<dyn AudioSource>, AudioError> {
+        Ok(match self {
+            AudioInput::Microphone(config) => Box::new(AudioCapture::start(config)?),
+            AudioInput::File(path) => Box::new(FileSource::open(&path)?),
+            AudioInput::Stdin(format) => Box::new(PcmReaderSource::stdin(format)),
+            AudioInput::Samples(samples) => Box::new(MemorySource::new(samples)),
+            AudioInput::Custom(factory) => factory()?,
+        })
+    }
+}
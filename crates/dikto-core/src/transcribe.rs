@@ -2,7 +2,7 @@ use std::path::Path;
 use thiserror::Error;
 use tracing::info;
 
-use parakeet_rs::{ParakeetTDT, Transcriber};
+use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 #[derive(Debug, Error)]
@@ -30,11 +30,103 @@ impl Default for TranscribeConfig {
     }
 }
 
-/// A segment of transcribed text.
-#[derive(Debug, Clone)]
+/// A word with its position in the audio, in seconds from the start of the buffer.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct WordTimestamp {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// A segment of transcribed text with its position in the audio (seconds).
+/// `words` is empty when the backend produced no word timings.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
 pub struct TranscriptSegment {
     pub text: String,
     pub is_final: bool,
+    pub start: f64,
+    pub end: f64,
+    pub words: Vec<WordTimestamp>,
+}
+
+impl TranscriptSegment {
+    /// Build a final segment spanning `words`, with text joined from the words.
+    pub fn from_words(words: Vec<WordTimestamp>) -> Self {
+        let text = words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            text,
+            is_final: true,
+            start: words.first().map_or(0.0, |w| w.start),
+            end: words.last().map_or(0.0, |w| w.end),
+            words,
+        }
+    }
+
+    /// Shift all times by `offset` seconds.
+    pub fn offset(&mut self, offset: f64) {
+        self.start += offset;
+        self.end += offset;
+        for word in &mut self.words {
+            word.start += offset;
+            word.end += offset;
+        }
+    }
+}
+
+/// Merge timed sub-word tokens into words. A token starting with a space or the
+/// SentencePiece marker `▁` begins a new word; other tokens (word pieces,
+/// punctuation, contractions) attach to the current word. Whitespace-only
+/// tokens end the current word.
+pub fn group_tokens_into_words(tokens: &[WordTimestamp]) -> Vec<WordTimestamp> {
+    let mut words: Vec<WordTimestamp> = Vec::new();
+    let mut current: Option<WordTimestamp> = None;
+
+    for token in tokens {
+        let trimmed = token.text.trim_start_matches(['▁', ' ']);
+        let starts_word = trimmed.len() != token.text.len();
+        if trimmed.trim().is_empty() {
+            words.extend(current.take());
+            continue;
+        }
+        match &mut current {
+            Some(word) if !starts_word => {
+                word.text.push_str(trimmed);
+                word.end = token.end;
+            }
+            _ => {
+                words.extend(current.take());
+                current = Some(WordTimestamp {
+                    text: trimmed.to_string(),
+                    start: token.start,
+                    end: token.end,
+                });
+            }
+        }
+    }
+    words.extend(current);
+    words
+}
+
+/// Split words into sentence segments, ending a segment after a word that
+/// ends with `.`, `?` or `!`.
+pub fn segments_from_words(words: Vec<WordTimestamp>) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut current = Vec::new();
+    for word in words {
+        let ends_sentence = word.text.ends_with(['.', '?', '!']);
+        current.push(word);
+        if ends_sentence {
+            segments.push(TranscriptSegment::from_words(std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        segments.push(TranscriptSegment::from_words(current));
+    }
+    segments
 }
 
 /// Parakeet TDT engine that keeps the model loaded in memory.
@@ -63,14 +155,37 @@ impl ParakeetEngine {
     }
 
     /// Run batch inference on audio samples.
-    /// Returns the transcribed text.
-    pub fn transcribe(&mut self, samples: &[f32]) -> Result<String, TranscribeError> {
+    /// Returns sentence segments with word timings from the TDT token durations.
+    pub fn transcribe(
+        &mut self,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptSegment>, TranscribeError> {
         let result = self
             .model
-            .transcribe_samples(samples.to_vec(), 16000, 1, None)
+            .transcribe_samples(samples.to_vec(), 16000, 1, Some(TimestampMode::Tokens))
             .map_err(|e| TranscribeError::Inference(e.to_string()))?;
 
-        Ok(result.text)
+        let tokens: Vec<WordTimestamp> = result
+            .tokens
+            .iter()
+            .map(|t| WordTimestamp {
+                text: t.text.clone(),
+                start: t.start as f64,
+                end: t.end as f64,
+            })
+            .collect();
+        let words = group_tokens_into_words(&tokens);
+        if words.is_empty() && !result.text.trim().is_empty() {
+            // No token timings: one segment spanning the whole buffer
+            return Ok(vec![TranscriptSegment {
+                text: result.text.trim().to_string(),
+                is_final: true,
+                start: 0.0,
+                end: samples.len() as f64 / 16000.0,
+                words: Vec::new(),
+            }]);
+        }
+        Ok(segments_from_words(words))
     }
 }
 
@@ -148,7 +263,12 @@ impl WhisperEngine {
 
     /// Run batch inference on audio samples.
     /// `language` should be an ISO-639-1 code (e.g. "en", "es") or "auto".
-    pub fn transcribe(&self, samples: &[f32], language: &str) -> Result<String, TranscribeError> {
+    /// Returns whisper's segments with word timings built from token timestamps.
+    pub fn transcribe(
+        &self,
+        samples: &[f32],
+        language: &str,
+    ) -> Result<Vec<TranscriptSegment>, TranscribeError> {
         let mut state = self
             .ctx
             .create_state()
@@ -162,9 +282,8 @@ impl WhisperEngine {
             params.set_language(Some(language));
         }
 
-        // Disable token timestamps for speed
-        params.set_token_timestamps(false);
-        // Single-segment mode
+        // Token timestamps give per-word timings
+        params.set_token_timestamps(true);
         params.set_single_segment(false);
         params.set_print_special(false);
         params.set_print_progress(false);
@@ -179,13 +298,48 @@ impl WhisperEngine {
             .full_n_segments()
             .map_err(|e| TranscribeError::Inference(format!("get segments: {e}")))?;
 
-        let mut text = String::new();
+        // Token ids at or above EOT are special tokens (timestamps, language, ...)
+        let token_eot = self.ctx.token_eot();
+        let mut segments = Vec::new();
         for i in 0..n_segments {
-            if let Ok(seg) = state.full_get_segment_text(i) {
-                text.push_str(&seg);
+            let Ok(text) = state.full_get_segment_text_lossy(i) else {
+                continue;
+            };
+            // Whisper times are in centiseconds
+            let start = state.full_get_segment_t0(i).unwrap_or(0) as f64 / 100.0;
+            let end = state.full_get_segment_t1(i).unwrap_or(0) as f64 / 100.0;
+
+            let n_tokens = state.full_n_tokens(i).unwrap_or(0);
+            let mut tokens = Vec::new();
+            for j in 0..n_tokens {
+                if state
+                    .full_get_token_id(i, j)
+                    .is_ok_and(|id| id >= token_eot)
+                {
+                    continue;
+                }
+                let (Ok(text), Ok(data)) = (
+                    state.full_get_token_text_lossy(i, j),
+                    state.full_get_token_data(i, j),
+                ) else {
+                    continue;
+                };
+                tokens.push(WordTimestamp {
+                    text,
+                    start: data.t0 as f64 / 100.0,
+                    end: data.t1 as f64 / 100.0,
+                });
             }
+
+            segments.push(TranscriptSegment {
+                text: text.trim().to_string(),
+                is_final: true,
+                start,
+                end,
+                words: group_tokens_into_words(&tokens),
+            });
         }
 
-        Ok(text)
+        Ok(segments)
     }
 }
@@ -0,0 +1,51 @@
+// Bookkeeping for the listening pipeline: which input audio belongs to the
+// utterance in the ASR session buffer, so its segments can be placed in
+// session time. Positions are sample indices since the session started.
+
+/// Tracks the utterance being recorded from its first confirmed speech until
+/// it is flushed.
+#[derive(Debug, Clone, Default)]
+pub struct UtteranceTracker {
+    active: bool,
+    /// Position of the first sample in the ASR session buffer.
+    start: usize,
+}
+
+impl UtteranceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The VAD confirmed speech; the session buffer will start at
+    /// `buffer_start` (the beginning of the pre-speech audio). Returns true if
+    /// this begins a new utterance, so the pre-speech audio must be fed.
+    ///
+    /// Speech that the VAD drops as too short never ends the utterance, so
+    /// its audio stays in the buffer and a later start keeps the first
+    /// position; otherwise every later segment would be placed too late.
+    pub fn speech_start(&mut self, buffer_start: usize) -> bool {
+        if self.active {
+            return false;
+        }
+        self.active = true;
+        self.start = buffer_start;
+        true
+    }
+
+    /// Whether input audio currently goes to the session buffer.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Position of the first sample of the current (or last) utterance.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The session buffer was flushed. Returns the start of the utterance
+    /// whose segments were just produced.
+    pub fn finish(&mut self) -> usize {
+        self.active = false;
+        self.start
+    }
+}
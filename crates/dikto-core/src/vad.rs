@@ -50,6 +50,24 @@ impl Default for VadConfig {
     }
 }
 
+/// Samples per Silero VAD frame at 16kHz (32ms).
+pub const VAD_FRAME_SAMPLES: usize = 512;
+
+/// Run Silero VAD over a whole buffer and return the speech probability of
+/// each 512-sample frame. A trailing partial frame is not scored.
+pub fn speech_probabilities(samples: &[f32]) -> Result<Vec<f32>, VadError> {
+    let mut detector = VoiceActivityDetector::builder()
+        .sample_rate(16000)
+        .chunk_size(VAD_FRAME_SAMPLES)
+        .build()
+        .map_err(|e| VadError::Init(e.to_string()))?;
+
+    Ok(samples
+        .chunks_exact(VAD_FRAME_SAMPLES)
+        .map(|frame| detector.predict(frame.iter().copied()))
+        .collect())
+}
+
 /// VAD processor that wraps Silero VAD and tracks speech state.
 pub struct VadProcessor {
     detector: VoiceActivityDetector,
@@ -63,6 +81,8 @@ pub struct VadProcessor {
     pending_speech_frames: u32,
     /// Samples per chunk (512 for 16kHz = 32ms).
     chunk_size: usize,
+    /// Speech probability of the last processed chunk.
+    last_probability: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -76,7 +96,7 @@ pub enum VadState {
 impl VadProcessor {
     /// Create a new VAD processor.
     pub fn new(config: VadConfig) -> Result<Self, VadError> {
-        let chunk_size = 512; // ~32ms at 16kHz
+        let chunk_size = VAD_FRAME_SAMPLES; // ~32ms at 16kHz
         let detector = VoiceActivityDetector::builder()
             .sample_rate(config.sample_rate as i64)
             .chunk_size(chunk_size)
@@ -91,6 +111,7 @@ impl VadProcessor {
             speech_frames: 0,
             pending_speech_frames: 0,
             chunk_size,
+            last_probability: 0.0,
         })
     }
 
@@ -98,6 +119,7 @@ impl VadProcessor {
     /// Input should be 512 samples at 16kHz (32ms).
     pub fn process_chunk(&mut self, samples: &[f32]) -> Result<VadEvent, VadError> {
         let probability = self.detector.predict(samples.iter().copied());
+        self.last_probability = probability;
 
         let is_speech = probability > self.config.speech_threshold;
         let frame_duration_ms =
@@ -192,4 +214,9 @@ impl VadProcessor {
     pub fn state(&self) -> VadState {
         self.state
     }
+
+    /// Speech probability of the last chunk passed to `process_chunk`.
+    pub fn last_probability(&self) -> f32 {
+        self.last_probability
+    }
 }
@@ -1,7 +1,47 @@
-// Tests for dikto_core::audio — AudioCaptureConfig defaults and AudioError
-// display messages. Actual audio capture requires hardware and is not tested.
+// Tests for dikto_core::audio — AudioCaptureConfig defaults, input device
+// selection, AudioError display messages, and the real-time InputProcessor (sample formats,
+// channel selection, overflow accounting, no allocations). Actual audio capture requires hardware and is
+// not tested.
 
-use dikto_core::audio::{AudioCaptureConfig, AudioError};
+use cpal::Sample;
+use dikto_core::audio::{find_device_or_default, AudioCaptureConfig, AudioError, InputProcessor};
+use dikto_core::resample::{ResampleQuality, Resampler};
+use ringbuf::traits::{Consumer, Observer, Split};
+use ringbuf::HeapRb;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counts heap allocations per thread, to check the real-time path.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
 
 // ---------------------------------------------------------------------------
 // AudioCaptureConfig defaults
@@ -27,9 +67,51 @@ fn custom_audio_config() {
     let config = AudioCaptureConfig {
         target_sample_rate: 44100,
         buffer_capacity: 44100 * 10,
+        device_name: Some("USB Headset".to_string()),
+        resample_quality: ResampleQuality::Best,
+        input_channels: vec![3],
     };
     assert_eq!(config.target_sample_rate, 44100);
     assert_eq!(config.buffer_capacity, 441000);
+    assert_eq!(config.device_name.as_deref(), Some("USB Headset"));
+    assert_eq!(config.resample_quality, ResampleQuality::Best);
+    assert_eq!(config.input_channels, vec![3]);
+}
+
+// ---------------------------------------------------------------------------
+// Device selection
+// ---------------------------------------------------------------------------
+
+fn devices() -> Vec<(String, &'static str)> {
+    vec![
+        ("Built-in Microphone".to_string(), "builtin"),
+        ("USB Headset".to_string(), "usb"),
+    ]
+}
+
+/// A connected device is found by name.
+#[test]
+fn find_device_by_name() {
+    let device = find_device_or_default(devices(), Some("USB Headset"), || Some("default"));
+    assert_eq!(device, Some("usb"));
+}
+
+/// An unplugged device falls back to the system default.
+#[test]
+fn missing_device_falls_back_to_default() {
+    let device = find_device_or_default(devices(), Some("Unplugged Mic"), || Some("default"));
+    assert_eq!(device, Some("default"));
+}
+
+/// Without a configured name the system default is used, even if a device
+/// of another name comes first.
+#[test]
+fn no_device_name_uses_default() {
+    assert_eq!(
+        find_device_or_default(devices(), None, || Some("default")),
+        Some("default")
+    );
+    assert_eq!(find_device_or_default(devices(), None, || None), None);
 }
 
 // ---------------------------------------------------------------------------
@@ -75,3 +157,154 @@ fn audio_error_device_display() {
     let msg = err.to_string();
     assert!(msg.contains("unplugged"));
 }
+
+// ---------------------------------------------------------------------------
+// InputProcessor
+// ---------------------------------------------------------------------------
+
+/// Interleaved channels are averaged to mono.
+#[test]
+fn processor_downmixes_to_mono() {
+    let (producer, mut consumer) = HeapRb::<f32>::new(1000).split();
+    let dropped = Arc::new(AtomicU64::new(0));
+    let resampler = Resampler::new(16000, 16000, ResampleQuality::Balanced);
+    let mut processor = InputProcessor::new(2, &[], resampler, producer, dropped);
+
+    let stereo: Vec<i16> = [16384, -8192].repeat(100);
+    processor.process(&stereo, |s: i16| s as f32 / 32768.0);
+    let mono: Vec<f32> = consumer.pop_iter().collect();
+    assert_eq!(mono.len(), 100);
+    assert!(mono.iter().all(|&x| (x - 0.125).abs() < 1e-6));
+}
+
+/// Mono signal from 16kHz interleaved input, with `input_channels` selected.
+fn process_channels(channels: u16, input_channels: &[u16], interleaved: &[f32]) -> Vec<f32> {
+    let (producer, mut consumer) = HeapRb::<f32>::new(10000).split();
+    let resampler = Resampler::new(16000, 16000, ResampleQuality::Balanced);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let mut processor = InputProcessor::new(channels, input_channels, resampler, producer, dropped);
+    processor.process(interleaved, |s| s);
+    consumer.pop_iter().collect()
+}
+
+/// A single selected channel is used as is; the others are ignored.
+#[test]
+fn processor_selects_one_channel() {
+    // Four-channel interface with the mic on channel 3
+    let frames: Vec<f32> = [0.9, -0.9, 0.25, 0.5].repeat(50);
+    let mono = process_channels(4, &[3], &frames);
+    assert_eq!(mono.len(), 50);
+    assert!(mono.iter().all(|&x| (x - 0.25).abs() < 1e-6));
+}
+
+/// Several selected channels are averaged.
+#[test]
+fn processor_mixes_selected_channels() {
+    let frames: Vec<f32> = [0.9, 0.1, 0.3, 0.5].repeat(50);
+    let mono = process_channels(4, &[2, 4], &frames);
+    assert!(mono.iter().all(|&x| (x - 0.3).abs() < 1e-6));
+}
+
+/// Channels the device doesn't have are ignored; with none left, all are used.
+#[test]
+fn processor_ignores_missing_channels() {
+    let frames: Vec<f32> = [0.2, 0.4].repeat(50);
+    let mono = process_channels(2, &[2, 5], &frames);
+    assert!(mono.iter().all(|&x| (x - 0.4).abs() < 1e-6));
+    let mono = process_channels(2, &[7], &frames);
+    assert!(mono.iter().all(|&x| (x - 0.3).abs() < 1e-6));
+}
+
+/// Every integer and float device format maps to -1.0..1.0, with unsigned
+/// formats centered on their midpoint.
+#[test]
+fn sample_formats_convert_to_f32() {
+    fn convert<T: Copy>(samples: &[T], to_f32: impl Fn(T) -> f32) -> Vec<f32> {
+        let (producer, mut consumer) = HeapRb::<f32>::new(100).split();
+        let resampler = Resampler::new(16000, 16000, ResampleQuality::Balanced);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let mut processor = InputProcessor::new(1, &[], resampler, producer, dropped);
+        processor.process(samples, to_f32);
+        consumer.pop_iter().collect()
+    }
+    let close = |a: &[f32], b: &[f32]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-2);
+
+    let expected = [-1.0, 0.0, 0.5];
+    assert!(close(
+        &convert(&[i8::MIN, 0, 64], f32::from_sample::<i8>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[i32::MIN, 0, 1 << 30], f32::from_sample::<i32>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[i64::MIN, 0, 1 << 62], f32::from_sample::<i64>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[0u8, 128, 192], f32::from_sample::<u8>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[0u16, 32768, 49152], f32::from_sample::<u16>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[0u32, 1 << 31, 3 << 30], f32::from_sample::<u32>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[0u64, 1 << 63, 3 << 62], f32::from_sample::<u64>),
+        &expected
+    ));
+    assert!(close(
+        &convert(&[-1.0f64, 0.0, 0.5], f32::from_sample::<f64>),
+        &expected
+    ));
+}
+
+/// Samples that don't fit in a full ring buffer are counted, not lost silently.
+#[test]
+fn processor_counts_dropped_samples() {
+    let (producer, consumer) = HeapRb::<f32>::new(1000).split();
+    let dropped = Arc::new(AtomicU64::new(0));
+    let resampler = Resampler::new(48000, 16000, ResampleQuality::Balanced);
+    let mut processor = InputProcessor::new(1, &[], resampler, producer, dropped.clone());
+
+    let input = vec![0.25f32; 48000];
+    for block in input.chunks(480) {
+        processor.process(block, |s| s);
+    }
+
+    // Everything the resampler produced either fits or is counted
+    let mut reference = Resampler::new(48000, 16000, ResampleQuality::Balanced);
+    let mut produced = Vec::new();
+    reference.process(&input, &mut produced);
+    assert_eq!(consumer.occupied_len(), 1000);
+    assert_eq!(
+        dropped.load(Ordering::Relaxed),
+        produced.len() as u64 - 1000
+    );
+}
+
+/// The callback path never touches the heap, including device buffers
+/// larger than one processing block.
+#[test]
+fn processor_does_not_allocate() {
+    let (producer, mut consumer) = HeapRb::<f32>::new(16000).split();
+    let dropped = Arc::new(AtomicU64::new(0));
+    let resampler = Resampler::new(44100, 16000, ResampleQuality::Best);
+    let mut processor = InputProcessor::new(2, &[], resampler, producer, dropped.clone());
+    let small = vec![0.1f32; 2 * 441];
+    let large = vec![0.1f32; 2 * 10000];
+
+    let before = allocations();
+    for _ in 0..20 {
+        processor.process(&small, |s| s);
+        processor.process(&large, |s| s);
+        consumer.clear();
+    }
+    assert_eq!(allocations() - before, 0);
+    assert_eq!(dropped.load(Ordering::Relaxed), 0);
+}
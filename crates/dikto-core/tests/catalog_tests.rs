@@ -0,0 +1,416 @@
+// Tests for dikto_core::catalog — catalog parsing rules, fetching with
+// checksum and signature verification, which entries an unsigned catalog may
+// contribute, and storing the cached catalog, against a local stand-in HTTP
+// server.
+
+mod common;
+
+use base64::Engine;
+use common::{fresh_dir, sha256_hex, ServerOptions, TestServer};
+use dikto_core::catalog::{
+    catalog_models, load_catalog, load_trusted_catalog, parse_catalog, refresh_catalog_to,
+    signature_path, verify_signature, without_builtin_updates,
+};
+use dikto_core::config::{catalog_path, models_dir, save_config, DiktoConfig};
+use dikto_core::models::{
+    find_model, is_model_downloaded, update_available, DownloadOptions, ModelError, MODELS,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use std::sync::{Mutex, MutexGuard, Once};
+
+const HASH_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+/// Catalog with an update for a built-in model and one new model.
+fn catalog_json() -> String {
+    format!(
+        r#"{{
+  "models": [
+    {{
+      "name": "whisper-tiny",
+      "backend": "whisper",
+      "description": "Whisper Tiny, re-exported",
+      "files": [
+        {{ "filename": "ggml-tiny.bin", "url": "https://example.com/ggml-tiny.bin", "size_mb": 75, "sha256": "{HASH_A}" }}
+      ]
+    }},
+    {{
+      "name": "whisper-medium",
+      "backend": "whisper",
+      "files": [
+        {{ "filename": "ggml-medium.bin", "url": "https://example.com/ggml-medium.bin", "size_mb": 1500, "sha256": "{HASH_A}" }}
+      ]
+    }}
+  ]
+}}"#
+    )
+}
+
+/// Point the home directory at an empty temp dir, for the tests of the
+/// catalog cached in the data dir. Shared by all tests of this file; the
+/// guard keeps them from changing the config and catalog at the same time.
+fn home() -> MutexGuard<'static, ()> {
+    static INIT: Once = Once::new();
+    static LOCK: Mutex<()> = Mutex::new(());
+    INIT.call_once(|| {
+        std::env::set_var("HOME", fresh_dir("dikto_catalog_tests_home"));
+    });
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Store `json` as the cached catalog, signed with `key`.
+fn install_signed_catalog(json: &str, key: &SigningKey) -> std::path::PathBuf {
+    let path = catalog_path().unwrap();
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(signature_path(&path), sign(key, json)).unwrap();
+    std::fs::write(&path, json).unwrap();
+    save_config(&DiktoConfig {
+        catalog_public_key: Some(public_key(key)),
+        ..DiktoConfig::default()
+    })
+    .unwrap();
+    path
+}
+
+fn base64(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(key: &SigningKey) -> String {
+    base64(key.verifying_key().as_bytes())
+}
+
+/// Base64 signature of `json`, wrapped like the output of `base64`.
+fn sign(key: &SigningKey, json: &str) -> String {
+    let signature = base64(&key.sign(json.as_bytes()).to_bytes());
+    format!("{}\n{}\n", &signature[..76], &signature[76..])
+}
+
+/// Server publishing `json` at /catalog.json with `checksum` at /catalog.json.sha256.
+fn catalog_server(json: &str, checksum: &str) -> TestServer {
+    signed_catalog_server(json, checksum, None)
+}
+
+/// Like `catalog_server`, with `signature` at /catalog.json.sig.
+fn signed_catalog_server(json: &str, checksum: &str, signature: Option<&str>) -> TestServer {
+    let mut routes = vec![
+        ("/catalog.json".to_string(), json.as_bytes().to_vec()),
+        (
+            "/catalog.json.sha256".to_string(),
+            format!("{checksum}  catalog.json\n").into_bytes(),
+        ),
+    ];
+    if let Some(signature) = signature {
+        routes.push((
+            "/catalog.json.sig".to_string(),
+            signature.as_bytes().to_vec(),
+        ));
+    }
+    TestServer::start(ServerOptions {
+        routes,
+        ..ServerOptions::new(b"not found".to_vec())
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Unlike the manifest, the catalog may use built-in names (as updates).
+#[test]
+fn catalog_allows_builtin_names() {
+    let models = parse_catalog(&catalog_json()).unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].name, "whisper-tiny");
+    assert_eq!(models[0].files[0].sha256, HASH_A);
+    assert_eq!(models[1].name, "whisper-medium");
+}
+
+/// Every catalog file needs a hash, or updates couldn't be detected.
+#[test]
+fn catalog_requires_sha256() {
+    let json = r#"{"models": [{"name": "m", "backend": "whisper",
+        "files": [{"filename": "m.bin", "url": "https://example.com/m.bin", "size_mb": 1}]}]}"#;
+    let err = parse_catalog(json).unwrap_err();
+    assert!(matches!(err, ModelError::InvalidCatalog(_)));
+    assert!(err.to_string().contains("sha256"), "got: {err}");
+}
+
+/// A missing cache file means an empty catalog.
+#[test]
+fn load_missing_catalog_is_empty() {
+    let path = fresh_dir("dikto_catalog_missing").join("catalog.json");
+    assert!(load_catalog(&path).unwrap().is_empty());
+}
+
+/// An unsigned catalog can't replace built-in models, or whoever serves it
+/// could point them at other files.
+#[test]
+fn unsigned_catalog_drops_builtin_updates() {
+    let models = without_builtin_updates(parse_catalog(&catalog_json()).unwrap());
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].name, "whisper-medium");
+}
+
+// ---------------------------------------------------------------------------
+// Signatures
+// ---------------------------------------------------------------------------
+
+/// A signature by the configured key verifies; wrapped base64 is fine.
+#[test]
+fn signature_verifies() {
+    let key = signing_key(1);
+    let json = catalog_json();
+    verify_signature(json.as_bytes(), &sign(&key, &json), &public_key(&key)).unwrap();
+}
+
+/// Another key's signature, or a signature of other content, is rejected.
+#[test]
+fn signature_mismatch() {
+    let json = catalog_json();
+    let signature = sign(&signing_key(1), &json);
+    let err =
+        verify_signature(json.as_bytes(), &signature, &public_key(&signing_key(2))).unwrap_err();
+    assert!(err.to_string().contains("signature mismatch"), "got: {err}");
+
+    let key = signing_key(1);
+    let err = verify_signature(b"{}", &signature, &public_key(&key)).unwrap_err();
+    assert!(err.to_string().contains("signature mismatch"), "got: {err}");
+}
+
+/// Malformed keys and signatures are errors, not panics.
+#[test]
+fn signature_malformed() {
+    let json = catalog_json();
+    let key = signing_key(1);
+    for (signature, public_key) in [
+        (sign(&key, &json), "not base64!".to_string()),
+        (sign(&key, &json), base64(&[0u8; 16])),
+        ("not base64!".to_string(), public_key(&key)),
+        (base64(&[0u8; 10]), public_key(&key)),
+    ] {
+        assert!(matches!(
+            verify_signature(json.as_bytes(), &signature, &public_key),
+            Err(ModelError::InvalidCatalog(_))
+        ));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fetching
+// ---------------------------------------------------------------------------
+
+/// A catalog matching its checksum is stored and loads back. Unsigned, it
+/// only adds models: the entry for a built-in model is dropped.
+#[tokio::test]
+async fn refresh_stores_verified_catalog() {
+    let json = catalog_json();
+    let server = catalog_server(&json, &sha256_hex(json.as_bytes()));
+    let path = fresh_dir("dikto_catalog_refresh").join("catalog.json");
+
+    let models = refresh_catalog_to(
+        &server.url_for("/catalog.json"),
+        &path,
+        None,
+        &DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].name, "whisper-medium");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), json);
+    assert_eq!(load_catalog(&path).unwrap().len(), 2);
+    assert_eq!(load_trusted_catalog(&path, None).unwrap().len(), 1);
+    assert!(!signature_path(&path).exists());
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+/// A checksum mismatch is rejected and leaves the cached catalog alone.
+#[tokio::test]
+async fn refresh_rejects_checksum_mismatch() {
+    let json = catalog_json();
+    let server = catalog_server(&json, HASH_A);
+    let path = fresh_dir("dikto_catalog_mismatch").join("catalog.json");
+
+    let err = refresh_catalog_to(
+        &server.url_for("/catalog.json"),
+        &path,
+        None,
+        &DownloadOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"), "got: {err}");
+    assert!(!path.exists());
+}
+
+/// A catalog without a published checksum is rejected.
+#[tokio::test]
+async fn refresh_requires_checksum_file() {
+    let json = catalog_json();
+    let server = TestServer::start(ServerOptions {
+        routes: vec![("/catalog.json".to_string(), json.into_bytes())],
+        ..ServerOptions::new(Vec::new())
+    });
+    let path = fresh_dir("dikto_catalog_no_checksum").join("catalog.json");
+
+    // The server answers unknown paths with an empty body, which is no valid checksum
+    let err = refresh_catalog_to(
+        &server.url_for("/catalog.json"),
+        &path,
+        None,
+        &DownloadOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ModelError::InvalidCatalog(_)), "got: {err}");
+    assert!(!path.exists());
+}
+
+/// With a public key, a signed catalog may update built-in models; the
+/// signature is stored next to it and checked again when loading.
+#[tokio::test]
+async fn refresh_signed_catalog() {
+    let key = signing_key(1);
+    let json = catalog_json();
+    let server = signed_catalog_server(
+        &json,
+        &sha256_hex(json.as_bytes()),
+        Some(&sign(&key, &json)),
+    );
+    let path = fresh_dir("dikto_catalog_signed").join("catalog.json");
+
+    let models = refresh_catalog_to(
+        &server.url_for("/catalog.json"),
+        &path,
+        Some(&public_key(&key)),
+        &DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].name, "whisper-tiny");
+    assert!(signature_path(&path).exists());
+    let loaded = load_trusted_catalog(&path, Some(&public_key(&key))).unwrap();
+    assert_eq!(loaded.len(), 2);
+
+    // A different configured key rejects the cached catalog
+    assert!(load_trusted_catalog(&path, Some(&public_key(&signing_key(2)))).is_err());
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+/// With a public key, a missing or wrong signature is rejected and nothing
+/// is stored, even if the checksum matches.
+#[tokio::test]
+async fn refresh_rejects_unsigned_catalog() {
+    let key = signing_key(1);
+    let json = catalog_json();
+    let checksum = sha256_hex(json.as_bytes());
+    for signature in [None, Some(sign(&signing_key(2), &json))] {
+        let server = signed_catalog_server(&json, &checksum, signature.as_deref());
+        let path = fresh_dir("dikto_catalog_unsigned").join("catalog.json");
+
+        let err = refresh_catalog_to(
+            &server.url_for("/catalog.json"),
+            &path,
+            Some(&public_key(&key)),
+            &DownloadOptions::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ModelError::HttpStatus(..) | ModelError::InvalidCatalog(_)
+            ),
+            "got: {err}"
+        );
+        assert!(!path.exists());
+        assert!(!signature_path(&path).exists());
+    }
+}
+
+/// A cached catalog changed after it was signed is rejected as a whole.
+#[test]
+fn load_rejects_tampered_catalog() {
+    let key = signing_key(1);
+    let json = catalog_json();
+    let path = fresh_dir("dikto_catalog_tampered").join("catalog.json");
+    std::fs::write(signature_path(&path), sign(&key, &json)).unwrap();
+    std::fs::write(&path, json.replace("example.com", "evil.example")).unwrap();
+
+    let err = load_trusted_catalog(&path, Some(&public_key(&key))).unwrap_err();
+    assert!(err.to_string().contains("signature mismatch"), "got: {err}");
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+// ---------------------------------------------------------------------------
+// Cached catalog
+// ---------------------------------------------------------------------------
+
+/// Updates of built-in models from the cached catalog only apply while its
+/// signing key is configured and the signature is there; changing either
+/// takes effect without the catalog itself changing.
+#[test]
+fn cached_catalog_trust_follows_config() {
+    let _home = home();
+    let key = signing_key(1);
+    let path = install_signed_catalog(&catalog_json(), &key);
+    let with_key = DiktoConfig {
+        catalog_public_key: Some(public_key(&key)),
+        ..DiktoConfig::default()
+    };
+    let has_builtin_update = || catalog_models().iter().any(|m| m.name == "whisper-tiny");
+    let tiny_description = || find_model("whisper-tiny").unwrap().description;
+
+    assert!(has_builtin_update());
+    assert_eq!(tiny_description(), "Whisper Tiny, re-exported");
+
+    // Key cleared: only the new model is left
+    save_config(&DiktoConfig::default()).unwrap();
+    assert!(!has_builtin_update());
+    assert!(catalog_models().iter().any(|m| m.name == "whisper-medium"));
+    assert_ne!(tiny_description(), "Whisper Tiny, re-exported");
+
+    // Key back, signature gone: the whole catalog is rejected
+    save_config(&with_key).unwrap();
+    assert!(has_builtin_update());
+    std::fs::remove_file(signature_path(&path)).unwrap();
+    assert!(catalog_models().is_empty());
+    assert_ne!(tiny_description(), "Whisper Tiny, re-exported");
+}
+
+/// A signed catalog adding a file to an installed built-in model offers an
+/// update; the installed version stays downloaded and usable.
+#[test]
+fn catalog_adding_file_is_update_of_installed_model() {
+    let _home = home();
+    let builtin = MODELS.iter().find(|m| m.name == "whisper-tiny").unwrap();
+    let json = format!(
+        r#"{{"models": [{{
+  "name": "whisper-tiny",
+  "backend": "whisper",
+  "files": [
+    {{ "filename": "ggml-tiny.bin", "url": "{}", "size_mb": 75, "sha256": "{}" }},
+    {{ "filename": "ggml-tiny-encoder.mlmodelc.zip", "url": "https://example.com/enc.zip", "size_mb": 15, "sha256": "{HASH_A}" }}
+  ]
+}}]}}"#,
+        builtin.files[0].url, builtin.files[0].sha256
+    );
+    install_signed_catalog(&json, &signing_key(1));
+    assert_eq!(find_model("whisper-tiny").unwrap().files.len(), 2);
+
+    let dir = models_dir().join("whisper-tiny");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("ggml-tiny.bin"), b"installed before the update").unwrap();
+    assert!(is_model_downloaded("whisper-tiny"));
+    assert!(update_available("whisper-tiny"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,182 @@
+// Tests for dikto_core::chunk — long-audio chunk planning at silence
+// boundaries with overlap, and stitching chunk results into one timeline.
+
+mod common;
+
+use common::segment;
+use dikto_core::chunk::{plan_chunks, stitch_segments, AudioChunk, ChunkConfig};
+use dikto_core::transcribe::TranscriptSegment;
+
+/// Small config: 10-sample frames, 100-sample chunks, 10-sample overlap.
+fn config() -> ChunkConfig {
+    ChunkConfig {
+        max_chunk_samples: 100,
+        overlap_samples: 10,
+        silence_threshold: 0.5,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// plan_chunks
+// ---------------------------------------------------------------------------
+
+/// Audio that fits in one chunk should not be split.
+#[test]
+fn plan_short_audio_single_chunk() {
+    let chunks = plan_chunks(80, &[], 10, &config());
+    assert_eq!(chunks, vec![AudioChunk { start: 0, end: 80 }]);
+}
+
+/// Empty audio should produce no chunks.
+#[test]
+fn plan_empty_audio() {
+    assert!(plan_chunks(0, &[], 10, &config()).is_empty());
+}
+
+/// Without silence information, chunks are cut at the max length with overlap.
+#[test]
+fn plan_without_silence_uses_hard_cuts() {
+    let chunks = plan_chunks(250, &[], 10, &config());
+    assert_eq!(
+        chunks,
+        vec![
+            AudioChunk { start: 0, end: 100 },
+            AudioChunk {
+                start: 90,
+                end: 190
+            },
+            AudioChunk {
+                start: 180,
+                end: 250
+            },
+        ]
+    );
+}
+
+/// The cut should land in the middle of the longest silence in the chunk's second half.
+#[test]
+fn plan_cuts_at_silence() {
+    // Speech everywhere except frames 6..8 (samples 60..80)
+    let mut probs = vec![0.9f32; 20];
+    probs[6] = 0.1;
+    probs[7] = 0.1;
+    let chunks = plan_chunks(200, &probs, 10, &config());
+    assert_eq!(chunks[0], AudioChunk { start: 0, end: 70 });
+    assert_eq!(chunks[1].start, 60);
+}
+
+/// Silence in the first half of a chunk is ignored (chunks stay reasonably long).
+#[test]
+fn plan_ignores_early_silence() {
+    let mut probs = vec![0.9f32; 20];
+    probs[2] = 0.0;
+    let chunks = plan_chunks(200, &probs, 10, &config());
+    assert_eq!(chunks[0].end, 100);
+}
+
+/// Chunks should cover the whole buffer with no gaps and respect the max length.
+#[test]
+fn plan_covers_everything() {
+    let probs: Vec<f32> = (0..100)
+        .map(|i| if i % 7 == 0 { 0.0 } else { 1.0 })
+        .collect();
+    let chunks = plan_chunks(1000, &probs, 10, &config());
+    assert_eq!(chunks.first().unwrap().start, 0);
+    assert_eq!(chunks.last().unwrap().end, 1000);
+    for pair in chunks.windows(2) {
+        assert!(pair[1].start < pair[0].end, "gap between {pair:?}");
+        assert!(pair[1].start > pair[0].start);
+    }
+    assert!(chunks.iter().all(|c| c.end - c.start <= 100));
+}
+
+// ---------------------------------------------------------------------------
+// stitch_segments
+// ---------------------------------------------------------------------------
+
+/// Segment times should be shifted by each chunk's start.
+#[test]
+fn stitch_offsets_times() {
+    // Chunks at 0..16000 and 16000..32000 samples with no overlap
+    let chunks = vec![
+        AudioChunk {
+            start: 0,
+            end: 16000,
+        },
+        AudioChunk {
+            start: 16000,
+            end: 32000,
+        },
+    ];
+    let results = vec![
+        vec![segment(&[("one", 0.1, 0.4)])],
+        vec![segment(&[("two", 0.1, 0.4)])],
+    ];
+    let stitched = stitch_segments(&chunks, results);
+    assert_eq!(stitched.len(), 2);
+    assert!((stitched[1].start - 1.1).abs() < 1e-9);
+    assert!((stitched[1].words[0].end - 1.4).abs() < 1e-9);
+}
+
+/// Words in the overlap should be taken once: from the earlier chunk before
+/// the overlap midpoint, from the later chunk after it.
+#[test]
+fn stitch_deduplicates_overlap() {
+    // Chunk A: 0-2s, chunk B: 1-3s; overlap 1-2s, midpoint 1.5s
+    let chunks = vec![
+        AudioChunk {
+            start: 0,
+            end: 32000,
+        },
+        AudioChunk {
+            start: 16000,
+            end: 48000,
+        },
+    ];
+    let results = vec![
+        vec![segment(&[
+            ("alpha", 0.2, 0.6),
+            ("beta", 1.1, 1.3),
+            ("gamma", 1.6, 1.9),
+        ])],
+        // Times relative to chunk B (starting at 1s)
+        vec![segment(&[
+            ("beta", 0.1, 0.3),
+            ("gamma", 0.6, 0.9),
+            ("delta", 1.2, 1.6),
+        ])],
+    ];
+    let stitched = stitch_segments(&chunks, results);
+    let text: Vec<&str> = stitched.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(text, vec!["alpha beta", "gamma delta"]);
+    assert!((stitched[1].start - 1.6).abs() < 1e-9);
+}
+
+/// Segments without word timings are kept by their midpoint.
+#[test]
+fn stitch_untimed_segments_by_midpoint() {
+    let chunks = vec![
+        AudioChunk {
+            start: 0,
+            end: 32000,
+        },
+        AudioChunk {
+            start: 16000,
+            end: 48000,
+        },
+    ];
+    let untimed = |text: &str, start: f64, end: f64| TranscriptSegment {
+        text: text.to_string(),
+        is_final: true,
+        start,
+        end,
+        words: Vec::new(),
+    };
+    let results = vec![
+        vec![untimed("first", 0.0, 1.2)],
+        vec![untimed("dup", 0.0, 0.2), untimed("second", 0.8, 2.0)],
+    ];
+    let stitched = stitch_segments(&chunks, results);
+    let text: Vec<&str> = stitched.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(text, vec!["first", "second"]);
+}
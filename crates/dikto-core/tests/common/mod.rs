@@ -0,0 +1,224 @@
+// Helpers shared by the integration tests: builders for test data, and a
+// stand-in HTTP server for download tests. The server serves one in-memory
+// body for every path (or per-path bodies), optionally honouring Range
+// requests, and records each request.
+
+#![allow(dead_code)]
+
+use dikto_core::transcribe::{TranscriptSegment, WordTimestamp};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+// ---------------------------------------------------------------------------
+// Test data
+// ---------------------------------------------------------------------------
+
+/// Lowercase hex SHA-256 of `bytes`, as in model definitions.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Empty directory `name` under the system temp dir.
+pub fn fresh_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Build a segment from (word, start, end) triples.
+pub fn segment(words: &[(&str, f64, f64)]) -> TranscriptSegment {
+    TranscriptSegment::from_words(
+        words
+            .iter()
+            .map(|(text, start, end)| WordTimestamp {
+                text: text.to_string(),
+                start: *start,
+                end: *end,
+            })
+            .collect(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Stand-in HTTP server
+// ---------------------------------------------------------------------------
+
+/// How the stand-in server behaves.
+#[derive(Debug, Clone)]
+pub struct ServerOptions {
+    /// Body served for every path not in `routes`.
+    pub body: Vec<u8>,
+    /// Bodies for specific paths.
+    pub routes: Vec<(String, Vec<u8>)>,
+    /// Answer `Range: bytes=N-` with 206 Partial Content (else ignore it and send 200).
+    pub support_range: bool,
+    /// Drop the connection after this many body bytes on the first request.
+    pub fail_first_after: Option<usize>,
+    /// Stop sending (but keep the connection open) after this many body bytes
+    /// on the first request.
+    pub stall_first_after: Option<usize>,
+    /// Answer the first request with this status code and an empty body.
+    pub status_first: Option<u16>,
+}
+
+impl ServerOptions {
+    pub fn new(body: Vec<u8>) -> Self {
+        Self {
+            body,
+            routes: Vec::new(),
+            support_range: true,
+            fail_first_after: None,
+            stall_first_after: None,
+            status_first: None,
+        }
+    }
+}
+
+/// A request as seen by the server.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub path: String,
+    pub range: Option<String>,
+}
+
+pub struct TestServer {
+    /// Base URL, e.g. `http://127.0.0.1:12345`.
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl TestServer {
+    /// Start a server on a free local port. It runs until the test process exits.
+    pub fn start(options: ServerOptions) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let served = Arc::new(AtomicUsize::new(0));
+        let options = Arc::new(options);
+
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let options = options.clone();
+                let recorded = recorded.clone();
+                let index = served.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || handle(stream, &options, &recorded, index));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Full URL for `path` (which should start with `/`).
+    pub fn url_for(&self, path: &str) -> String {
+        format!("{}{path}", self.url)
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    options: &ServerOptions,
+    recorded: &Mutex<Vec<RecordedRequest>>,
+    index: usize,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .to_string();
+
+    let mut range = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+    let full_body = options
+        .routes
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, b)| b)
+        .unwrap_or(&options.body);
+    recorded.lock().unwrap().push(RecordedRequest {
+        path,
+        range: range.clone(),
+    });
+
+    if let Some(status) = options.status_first.filter(|_| index == 0) {
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {status} Test Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+        return;
+    }
+
+    let total = full_body.len();
+    let start = range
+        .as_deref()
+        .filter(|_| options.support_range)
+        .and_then(|r| r.strip_prefix("bytes="))
+        .and_then(|r| r.trim_end_matches('-').parse::<usize>().ok());
+
+    let (status, headers, body) = match start {
+        Some(start) if start >= total => (
+            "416 Range Not Satisfiable",
+            format!("Content-Range: bytes */{total}\r\nContent-Length: 0\r\n"),
+            &full_body[0..0],
+        ),
+        Some(start) => (
+            "206 Partial Content",
+            format!(
+                "Content-Range: bytes {start}-{}/{total}\r\nContent-Length: {}\r\n",
+                total - 1,
+                total - start
+            ),
+            &full_body[start..],
+        ),
+        None => (
+            "200 OK",
+            format!("Content-Length: {total}\r\n"),
+            &full_body[..],
+        ),
+    };
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{headers}Connection: close\r\n\r\n"
+    );
+    match (options.fail_first_after, options.stall_first_after) {
+        (Some(limit), _) if index == 0 => {
+            let _ = stream.write_all(&body[..limit.min(body.len())]);
+            let _ = stream.flush();
+            // Dropping the socket mid-body makes the client see a truncated response
+        }
+        (_, Some(limit)) if index == 0 => {
+            let _ = stream.write_all(&body[..limit.min(body.len())]);
+            let _ = stream.flush();
+            std::thread::sleep(std::time::Duration::from_secs(30));
+        }
+        _ => {
+            let _ = stream.write_all(body);
+        }
+    }
+}
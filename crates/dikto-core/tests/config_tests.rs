@@ -3,8 +3,9 @@
 
 use dikto_core::config::{
     config_dir, config_path, data_dir, default_model_name, is_valid_shortcut, models_dir,
-    ActivationMode, DiktoConfig,
+    ActivationMode, DiktoConfig, MAX_DURATION_SECS,
 };
+use dikto_core::resample::ResampleQuality;
 
 // ---------------------------------------------------------------------------
 // Default config
@@ -23,6 +24,35 @@ fn default_config_has_expected_values() {
     assert_eq!(config.activation_mode, ActivationMode::Hold);
     assert!(config.auto_paste);
     assert!(config.auto_copy);
+    assert!(config.keep_partial_downloads);
+    assert_eq!(config.download_retries, 3);
+    assert!(config.download_proxy.is_none());
+    assert!(config.download_mirror.is_none());
+    assert_eq!(config.resample_quality, ResampleQuality::Balanced);
+    assert!(config.input_channels.is_empty());
+    assert!(!config.save_audio);
+    assert_eq!(config.save_audio_max_files, 50);
+    assert_eq!(config.save_audio_max_age_days, 7);
+}
+
+/// validate() drops channel 0 (channels are 1-based) and duplicates.
+#[test]
+fn validate_input_channels() {
+    let mut config = DiktoConfig {
+        input_channels: vec![0, 3, 1, 3],
+        ..DiktoConfig::default()
+    };
+    config.validate();
+    assert_eq!(config.input_channels, vec![3, 1]);
+}
+
+/// resample_quality is stored in lowercase.
+#[test]
+fn resample_quality_serde() {
+    let config: DiktoConfig = serde_json::from_str(r#"{"resample_quality": "best"}"#).unwrap();
+    assert_eq!(config.resample_quality, ResampleQuality::Best);
+    let json = serde_json::to_value(DiktoConfig::default()).unwrap();
+    assert_eq!(json["resample_quality"], "balanced");
 }
 
 /// default_model_name() should match the default config.
@@ -66,6 +96,7 @@ fn deserialize_empty_json_object_gives_defaults() {
     assert_eq!(config.global_shortcut, Some("option+space".to_string()));
     assert!(config.auto_paste);
     assert!(config.auto_copy);
+    assert!(config.keep_partial_downloads);
 }
 
 /// Corrupt JSON should fail to parse.
@@ -251,15 +282,26 @@ fn validate_resets_too_long_language() {
 // Validate — numeric clamping
 // ---------------------------------------------------------------------------
 
-/// max_duration above 120 should be clamped to 120.
+/// max_duration above one hour should be clamped to MAX_DURATION_SECS.
 #[test]
 fn validate_clamps_max_duration_high() {
     let mut config = DiktoConfig {
-        max_duration: 999,
+        max_duration: 99_999,
         ..DiktoConfig::default()
     };
     config.validate();
-    assert_eq!(config.max_duration, 120);
+    assert_eq!(config.max_duration, MAX_DURATION_SECS);
+}
+
+/// Long dictation durations (beyond the old 120s cap) should be kept.
+#[test]
+fn validate_keeps_long_max_duration() {
+    let mut config = DiktoConfig {
+        max_duration: 900,
+        ..DiktoConfig::default()
+    };
+    config.validate();
+    assert_eq!(config.max_duration, 900);
 }
 
 /// max_duration of 0 should be clamped to 1.
@@ -317,6 +359,40 @@ fn validate_clamps_speech_threshold_low() {
     assert!((config.speech_threshold - 0.01).abs() < f32::EPSILON);
 }
 
+/// Download retries and timeouts are clamped to sane ranges.
+#[test]
+fn validate_clamps_download_settings() {
+    let mut config = DiktoConfig {
+        download_retries: 1000,
+        download_connect_timeout_secs: 0,
+        download_read_timeout_secs: 100_000,
+        download_parallel_files: 0,
+        ..Default::default()
+    };
+    config.validate();
+    assert_eq!(config.download_parallel_files, 1);
+    assert_eq!(config.download_retries, 10);
+    assert_eq!(config.download_connect_timeout_secs, 1);
+    assert_eq!(config.download_read_timeout_secs, 600);
+}
+
+/// Empty proxy, mirror and catalog strings are treated as unset.
+#[test]
+fn validate_clears_empty_download_urls() {
+    let mut config = DiktoConfig {
+        download_proxy: Some(" ".to_string()),
+        download_mirror: Some(String::new()),
+        catalog_url: Some(String::new()),
+        catalog_public_key: Some("  ".to_string()),
+        ..Default::default()
+    };
+    config.validate();
+    assert!(config.download_proxy.is_none());
+    assert!(config.download_mirror.is_none());
+    assert!(config.catalog_url.is_none());
+    assert!(config.catalog_public_key.is_none());
+}
+
 // ---------------------------------------------------------------------------
 // Serialize / roundtrip
 // ---------------------------------------------------------------------------
@@ -334,6 +410,21 @@ fn serialize_deserialize_roundtrip() {
         auto_paste: false,
         auto_copy: true,
         activation_mode: ActivationMode::Toggle,
+        input_device: Some("USB Headset".to_string()),
+        input_channels: vec![3],
+        resample_quality: ResampleQuality::Fast,
+        keep_partial_downloads: false,
+        download_retries: 5,
+        download_connect_timeout_secs: 10,
+        download_read_timeout_secs: 120,
+        download_proxy: Some("http://proxy.local:3128".to_string()),
+        download_mirror: Some("https://hf-mirror.com".to_string()),
+        download_parallel_files: 2,
+        catalog_url: Some("https://example.com/catalog.json".to_string()),
+        catalog_public_key: Some("MCowBQYDK2VwAyEA".to_string()),
+        save_audio: true,
+        save_audio_max_files: 10,
+        save_audio_max_age_days: 0,
     };
     let json = serde_json::to_string_pretty(&original).unwrap();
     let loaded: DiktoConfig = serde_json::from_str(&json).unwrap();
@@ -346,6 +437,33 @@ fn serialize_deserialize_roundtrip() {
     assert!(!loaded.auto_paste);
     assert!(loaded.auto_copy);
     assert_eq!(loaded.activation_mode, ActivationMode::Toggle);
+    assert_eq!(loaded.input_device, Some("USB Headset".to_string()));
+    assert_eq!(loaded.input_channels, vec![3]);
+    assert_eq!(loaded.resample_quality, ResampleQuality::Fast);
+    assert!(!loaded.keep_partial_downloads);
+    assert_eq!(loaded.download_retries, 5);
+    assert_eq!(loaded.download_parallel_files, 2);
+    assert_eq!(
+        loaded.catalog_url,
+        Some("https://example.com/catalog.json".to_string())
+    );
+    assert_eq!(
+        loaded.catalog_public_key,
+        Some("MCowBQYDK2VwAyEA".to_string())
+    );
+    assert_eq!(loaded.download_connect_timeout_secs, 10);
+    assert_eq!(loaded.download_read_timeout_secs, 120);
+    assert!(loaded.save_audio);
+    assert_eq!(loaded.save_audio_max_files, 10);
+    assert_eq!(loaded.save_audio_max_age_days, 0);
+    assert_eq!(
+        loaded.download_proxy,
+        Some("http://proxy.local:3128".to_string())
+    );
+    assert_eq!(
+        loaded.download_mirror,
+        Some("https://hf-mirror.com".to_string())
+    );
 }
 
 /// Write config to a temp file and read it back — file-level roundtrip.
@@ -366,6 +484,21 @@ fn save_load_file_roundtrip() {
         auto_paste: false,
         auto_copy: true,
         activation_mode: ActivationMode::Toggle,
+        input_device: None,
+        input_channels: Vec::new(),
+        resample_quality: ResampleQuality::Balanced,
+        keep_partial_downloads: true,
+        download_retries: 3,
+        download_connect_timeout_secs: 15,
+        download_read_timeout_secs: 60,
+        download_proxy: None,
+        download_mirror: None,
+        download_parallel_files: 3,
+        catalog_url: None,
+        catalog_public_key: None,
+        save_audio: false,
+        save_audio_max_files: 50,
+        save_audio_max_age_days: 7,
     };
 
     let json = serde_json::to_string_pretty(&original).unwrap();
@@ -0,0 +1,145 @@
+// Tests for dikto_core::decode — file decoding to 16kHz mono, downmixing,
+// linear resampling, and DecodeError paths.
+
+use dikto_core::decode::{
+    decode_bytes, decode_file, downmix, resample_linear, DecodeError, TARGET_SAMPLE_RATE,
+};
+use std::path::PathBuf;
+
+/// Write a WAV file with a constant value per channel and return its path.
+fn write_wav(name: &str, sample_rate: u32, channels: u16, secs: f32, values: &[f32]) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    let frames = (sample_rate as f32 * secs) as usize;
+    for _ in 0..frames {
+        for value in values.iter().take(channels as usize) {
+            writer.write_sample(*value).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+    path
+}
+
+// ---------------------------------------------------------------------------
+// decode_file
+// ---------------------------------------------------------------------------
+
+/// A 16kHz mono WAV should decode to the same number of samples.
+#[test]
+fn decode_wav_16khz_mono() {
+    let path = write_wav("dikto_decode_16k_mono.wav", 16000, 1, 1.0, &[0.25]);
+    let samples = decode_file(&path).unwrap();
+    assert_eq!(samples.len(), 16000);
+    assert!((samples[100] - 0.25).abs() < 1e-4);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// A 48kHz stereo WAV should be downmixed and resampled to 16kHz mono.
+#[test]
+fn decode_wav_48khz_stereo_to_16khz_mono() {
+    let path = write_wav("dikto_decode_48k_stereo.wav", 48000, 2, 2.0, &[0.5, 0.1]);
+    let samples = decode_file(&path).unwrap();
+    let expected = (2.0 * TARGET_SAMPLE_RATE as f32) as usize;
+    assert!(
+        samples.len().abs_diff(expected) <= 2,
+        "expected ~{expected} samples, got {}",
+        samples.len()
+    );
+    // Average of the two channels
+    assert!((samples[samples.len() / 2] - 0.3).abs() < 1e-3);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// A missing file should produce DecodeError::Open.
+#[test]
+fn decode_missing_file_is_open_error() {
+    let result = decode_file(std::path::Path::new("/tmp/dikto_decode_missing_xyz.wav"));
+    assert!(matches!(result, Err(DecodeError::Open(_))));
+}
+
+/// A file that is not audio should produce DecodeError::UnsupportedFormat.
+#[test]
+fn decode_garbage_is_unsupported_format() {
+    let path = std::env::temp_dir().join("dikto_decode_garbage.bin");
+    std::fs::write(&path, b"definitely not an audio file").unwrap();
+    let result = decode_file(&path);
+    assert!(matches!(result, Err(DecodeError::UnsupportedFormat(_))));
+    let _ = std::fs::remove_file(&path);
+}
+
+/// In-memory WAV bytes should decode like the file on disk.
+#[test]
+fn decode_bytes_wav() {
+    let path = write_wav("dikto_decode_bytes.wav", 16000, 1, 0.5, &[0.5]);
+    let bytes = std::fs::read(&path).unwrap();
+    let samples = decode_bytes(bytes, Some("wav")).unwrap();
+    assert_eq!(samples.len(), 8000);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Non-audio bytes should produce DecodeError::UnsupportedFormat.
+#[test]
+fn decode_bytes_garbage_is_unsupported_format() {
+    let result = decode_bytes(b"not audio".to_vec(), None);
+    assert!(matches!(result, Err(DecodeError::UnsupportedFormat(_))));
+}
+
+// ---------------------------------------------------------------------------
+// downmix
+// ---------------------------------------------------------------------------
+
+/// Mono input should pass through unchanged.
+#[test]
+fn downmix_mono_passthrough() {
+    let input = vec![0.1, 0.2, 0.3];
+    assert_eq!(downmix(&input, 1), input);
+}
+
+/// Stereo frames should be averaged.
+#[test]
+fn downmix_stereo_averages_frames() {
+    let input = vec![1.0, 0.0, 0.5, 0.5, -1.0, 1.0];
+    assert_eq!(downmix(&input, 2), vec![0.5, 0.5, 0.0]);
+}
+
+// ---------------------------------------------------------------------------
+// resample_linear
+// ---------------------------------------------------------------------------
+
+/// Equal rates should return the input unchanged.
+#[test]
+fn resample_same_rate_is_identity() {
+    let input = vec![0.1, 0.2, 0.3, 0.4];
+    assert_eq!(resample_linear(&input, 16000, 16000), input);
+}
+
+/// Downsampling 48kHz → 16kHz should produce a third of the samples.
+#[test]
+fn resample_48k_to_16k_length() {
+    let input = vec![0.0f32; 48000];
+    assert_eq!(resample_linear(&input, 48000, 16000).len(), 16000);
+}
+
+/// Empty input should produce empty output.
+#[test]
+fn resample_empty_input() {
+    assert!(resample_linear(&[], 44100, 16000).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// DecodeError display
+// ---------------------------------------------------------------------------
+
+/// DecodeError::NoAudioTrack should produce a human-readable message.
+#[test]
+fn decode_error_no_audio_track_display() {
+    assert!(DecodeError::NoAudioTrack
+        .to_string()
+        .contains("No audio track"));
+}
@@ -0,0 +1,681 @@
+// Tests for dikto_core::models downloads — fresh downloads, Range resume of
+// partial files, servers without Range support, verification of resumed
+// files, cancellation, retries, timeouts, mirrors, parallel multi-file
+// downloads, repair and updates of installed models, against a local
+// stand-in HTTP server.
+
+mod common;
+
+use common::{fresh_dir, sha256_hex, ServerOptions, TestServer};
+use dikto_core::models::{
+    download_model_files, read_install_record, repair_model_files, DownloadOptions, FileProgress,
+    ModelBackend, ModelError, ModelFile, ModelInfo,
+};
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Deterministic test payload of `len` bytes.
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Single-file model served from `url`. Strings are leaked to get the
+/// `&'static` lifetimes the registry uses.
+fn model_for(url: String, sha256: String) -> ModelInfo {
+    let files = vec![ModelFile {
+        filename: "ggml-test.bin",
+        url: Box::leak(url.into_boxed_str()),
+        size_mb: 0,
+        sha256: Box::leak(sha256.into_boxed_str()),
+    }];
+    ModelInfo {
+        name: "download-test",
+        size_mb: 0,
+        description: "",
+        files: Box::leak(files.into_boxed_slice()),
+        backend: ModelBackend::Whisper,
+    }
+}
+
+/// Parakeet-style model of `filenames`, all served by `server` with the same body.
+fn multi_file_model(server: &TestServer, filenames: &[&'static str], sha256: &str) -> ModelInfo {
+    let files: Vec<ModelFile> = filenames
+        .iter()
+        .map(|name| ModelFile {
+            filename: name,
+            url: Box::leak(server.url_for(&format!("/{name}")).into_boxed_str()),
+            size_mb: 0,
+            sha256: Box::leak(sha256.to_string().into_boxed_str()),
+        })
+        .collect();
+    ModelInfo {
+        name: "download-test-multi",
+        size_mb: 0,
+        description: "",
+        files: Box::leak(files.into_boxed_slice()),
+        backend: ModelBackend::Parakeet,
+    }
+}
+
+/// Options that fail on the first error, for tests of a single attempt.
+fn no_retries() -> DownloadOptions {
+    DownloadOptions {
+        retries: 0,
+        ..Default::default()
+    }
+}
+
+/// Options that retry quickly.
+fn fast_retries(retries: u32) -> DownloadOptions {
+    DownloadOptions {
+        retries,
+        initial_backoff: Duration::from_millis(10),
+        ..Default::default()
+    }
+}
+
+/// Fresh, empty temp directory for one test.
+/// Collects (downloaded, total) progress reports.
+fn progress_log() -> (Arc<Mutex<Vec<u64>>>, impl Fn(u64, u64) + Send + 'static) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = log.clone();
+    (log, move |downloaded, _total| {
+        sink.lock().unwrap().push(downloaded)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Fresh downloads
+// ---------------------------------------------------------------------------
+
+/// A download without a partial file fetches everything and installs it.
+#[tokio::test]
+async fn download_fresh_file() {
+    let body = payload(100_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_fresh");
+
+    let (log, on_progress) = progress_log();
+    download_model_files(
+        &model,
+        &dir,
+        &DownloadOptions::default(),
+        on_progress,
+        |_| {},
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    assert!(!dir.join("ggml-test.bin.downloading").exists());
+    assert!(server.requests()[0].range.is_none());
+    assert_eq!(*log.lock().unwrap().last().unwrap(), 100_000);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Resume
+// ---------------------------------------------------------------------------
+
+/// An existing partial file is resumed with a Range request.
+#[tokio::test]
+async fn download_resumes_partial_file() {
+    let body = payload(100_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_resume");
+    std::fs::write(dir.join("ggml-test.bin.downloading"), &body[..40_000]).unwrap();
+
+    let (log, on_progress) = progress_log();
+    download_model_files(
+        &model,
+        &dir,
+        &DownloadOptions::default(),
+        on_progress,
+        |_| {},
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].range.as_deref(), Some("bytes=40000-"));
+    // Progress starts from the bytes already on disk
+    assert!(log.lock().unwrap().iter().all(|&b| b >= 40_000));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// If the server ignores Range, the partial file is discarded and rewritten.
+#[tokio::test]
+async fn download_restarts_without_range_support() {
+    let body = payload(50_000);
+    let server = TestServer::start(ServerOptions {
+        support_range: false,
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_norange");
+    std::fs::write(dir.join("ggml-test.bin.downloading"), &body[..20_000]).unwrap();
+
+    download_model_files(&model, &dir, &DownloadOptions::default(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+
+    // Appending the full body would have doubled the first 20 kB
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Without retries, an interrupted download keeps its partial file, and the
+/// next call resumes it.
+#[tokio::test]
+async fn download_interrupted_then_resumed() {
+    let body = payload(200_000);
+    let server = TestServer::start(ServerOptions {
+        fail_first_after: Some(60_000),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_interrupted");
+    let partial = dir.join("ggml-test.bin.downloading");
+
+    assert!(
+        download_model_files(&model, &dir, &no_retries(), |_, _| {}, |_| {})
+            .await
+            .is_err()
+    );
+    let kept = std::fs::metadata(&partial).unwrap().len();
+    assert!(kept > 0 && kept < 200_000, "kept {kept} bytes");
+
+    download_model_files(&model, &dir, &DownloadOptions::default(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    assert_eq!(
+        server.requests()[1].range.as_deref(),
+        Some(format!("bytes={kept}-").as_str())
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A complete partial file (server answers 416) is verified and installed.
+#[tokio::test]
+async fn download_complete_partial_file_is_installed() {
+    let body = payload(30_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_complete_partial");
+    std::fs::write(dir.join("ggml-test.bin.downloading"), &body).unwrap();
+
+    download_model_files(&model, &dir, &DownloadOptions::default(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// The hash covers the whole file: a corrupt partial fails verification and is deleted.
+#[tokio::test]
+async fn download_corrupt_partial_fails_hash() {
+    let body = payload(80_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_corrupt");
+    std::fs::write(dir.join("ggml-test.bin.downloading"), vec![0u8; 10_000]).unwrap();
+
+    let err = download_model_files(&model, &dir, &DownloadOptions::default(), |_, _| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("SHA-256 mismatch"), "got: {err}");
+    assert!(!dir.join("ggml-test.bin.downloading").exists());
+    assert!(!dir.join("ggml-test.bin").exists());
+
+    // The next attempt starts from scratch and succeeds
+    download_model_files(&model, &dir, &DownloadOptions::default(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// HTTP errors are reported and leave no installed file.
+#[tokio::test]
+async fn download_http_error() {
+    let dir = fresh_dir("dikto_download_http_error");
+    // Nothing listens on port 9 (discard) on loopback
+    let model = model_for(
+        "http://127.0.0.1:9/ggml-test.bin".to_string(),
+        String::new(),
+    );
+    assert!(
+        download_model_files(&model, &dir, &no_retries(), |_, _| {}, |_| {})
+            .await
+            .is_err()
+    );
+    assert!(!dir.join("ggml-test.bin").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Cancellation
+// ---------------------------------------------------------------------------
+
+/// Start a download from a server that stalls after 30 kB, cancel it once
+/// those bytes arrived, and return the result and the partial file path.
+async fn cancel_stalled_download(dir_name: &str, keep_partial: bool) -> (ModelError, PathBuf) {
+    let body = payload(100_000);
+    let server = TestServer::start(ServerOptions {
+        stall_first_after: Some(30_000),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir(dir_name);
+    let options = DownloadOptions {
+        keep_partial_on_cancel: keep_partial,
+        ..Default::default()
+    };
+
+    let cancel = options.cancel.clone();
+    let on_progress = move |downloaded, _total| {
+        if downloaded >= 30_000 {
+            cancel.store(true, Ordering::Release);
+        }
+    };
+    let started = Instant::now();
+    let err = download_model_files(&model, &dir, &options, on_progress, |_| {})
+        .await
+        .unwrap_err();
+    // The stalled connection must not delay cancellation
+    assert!(started.elapsed() < Duration::from_secs(10));
+    (err, dir.join("ggml-test.bin.downloading"))
+}
+
+/// Cancelling with the keep policy leaves the partial file for resuming.
+#[tokio::test]
+async fn cancel_keeps_partial_file() {
+    let (err, partial) = cancel_stalled_download("dikto_download_cancel_keep", true).await;
+    assert!(matches!(err, ModelError::Cancelled), "got: {err}");
+    assert_eq!(std::fs::metadata(&partial).unwrap().len(), 30_000);
+
+    let _ = std::fs::remove_dir_all(partial.parent().unwrap());
+}
+
+/// Cancelling with the delete policy removes the partial file.
+#[tokio::test]
+async fn cancel_deletes_partial_file() {
+    let (err, partial) = cancel_stalled_download("dikto_download_cancel_delete", false).await;
+    assert!(matches!(err, ModelError::Cancelled), "got: {err}");
+    assert!(!partial.exists());
+
+    let _ = std::fs::remove_dir_all(partial.parent().unwrap());
+}
+
+/// A download cancelled before it starts makes no request.
+#[tokio::test]
+async fn cancel_before_start() {
+    let server = TestServer::start(ServerOptions::new(payload(1000)));
+    let model = model_for(server.url_for("/ggml-test.bin"), String::new());
+    let dir = fresh_dir("dikto_download_cancel_early");
+    let options = DownloadOptions::default();
+    options.cancel.store(true, Ordering::Release);
+
+    let err = download_model_files(&model, &dir, &options, |_, _| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ModelError::Cancelled));
+    assert!(server.requests().is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Retries, timeouts and mirrors
+// ---------------------------------------------------------------------------
+
+/// A dropped connection is retried within the same call, resuming the partial file.
+#[tokio::test]
+async fn retry_resumes_after_dropped_connection() {
+    let body = payload(200_000);
+    let server = TestServer::start(ServerOptions {
+        fail_first_after: Some(60_000),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_retry_dropped");
+
+    download_model_files(&model, &dir, &fast_retries(2), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].range.is_some());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Server errors (5xx) are retried.
+#[tokio::test]
+async fn retry_after_server_error() {
+    let body = payload(10_000);
+    let server = TestServer::start(ServerOptions {
+        status_first: Some(503),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_retry_503");
+
+    download_model_files(&model, &dir, &fast_retries(1), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    assert_eq!(server.requests().len(), 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Client errors such as 404 fail at once, without retries.
+#[tokio::test]
+async fn no_retry_after_not_found() {
+    let server = TestServer::start(ServerOptions {
+        status_first: Some(404),
+        ..ServerOptions::new(payload(1000))
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), String::new());
+    let dir = fresh_dir("dikto_download_no_retry_404");
+
+    let err = download_model_files(&model, &dir, &fast_retries(3), |_, _| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ModelError::HttpStatus(404, _)), "got: {err}");
+    assert_eq!(server.requests().len(), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A stalled transfer hits the read timeout and is resumed by a retry.
+#[tokio::test]
+async fn read_timeout_then_retry() {
+    let body = payload(100_000);
+    let server = TestServer::start(ServerOptions {
+        stall_first_after: Some(40_000),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_read_timeout");
+    let options = DownloadOptions {
+        read_timeout: Duration::from_millis(300),
+        ..fast_retries(1)
+    };
+
+    let started = Instant::now();
+    download_model_files(&model, &dir, &options, |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    assert_eq!(server.requests()[1].range.as_deref(), Some("bytes=40000-"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// With a mirror, files are fetched from the mirror under the original path.
+#[tokio::test]
+async fn download_from_mirror() {
+    let body = payload(20_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(
+        "https://huggingface.co/org/repo/resolve/main/ggml-test.bin".to_string(),
+        sha256_hex(&body),
+    );
+    let dir = fresh_dir("dikto_download_mirror");
+    let options = DownloadOptions {
+        mirror: Some(format!("{}/", server.url)),
+        ..no_retries()
+    };
+
+    download_model_files(&model, &dir, &options, |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(dir.join("ggml-test.bin")).unwrap(), body);
+    assert_eq!(
+        server.requests()[0].path,
+        "/org/repo/resolve/main/ggml-test.bin"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// An unparseable proxy URL is reported before any request is made.
+#[tokio::test]
+async fn invalid_proxy_is_rejected() {
+    let server = TestServer::start(ServerOptions::new(payload(1000)));
+    let model = model_for(server.url_for("/ggml-test.bin"), String::new());
+    let dir = fresh_dir("dikto_download_bad_proxy");
+    let options = DownloadOptions {
+        proxy: Some("not a url".to_string()),
+        ..no_retries()
+    };
+
+    let err = download_model_files(&model, &dir, &options, |_, _| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid proxy"), "got: {err}");
+    assert!(server.requests().is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Parallel multi-file downloads
+// ---------------------------------------------------------------------------
+
+const MULTI_FILES: &[&str] = &["encoder.onnx", "encoder.onnx.data", "decoder.onnx"];
+
+/// Files download concurrently: while one stalls, the others are fetched,
+/// and progress sums up to the size of all files.
+#[tokio::test]
+async fn parallel_files_download_concurrently() {
+    let body = payload(50_000);
+    let server = TestServer::start(ServerOptions {
+        stall_first_after: Some(20_000),
+        ..ServerOptions::new(body.clone())
+    });
+    let model = multi_file_model(&server, MULTI_FILES, &sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_parallel");
+    let options = DownloadOptions {
+        parallel_files: 3,
+        read_timeout: Duration::from_millis(300),
+        ..fast_retries(1)
+    };
+
+    let totals = Arc::new(Mutex::new(Vec::new()));
+    let totals_sink = totals.clone();
+    let files = Arc::new(Mutex::new(Vec::<FileProgress>::new()));
+    let files_sink = files.clone();
+    download_model_files(
+        &model,
+        &dir,
+        &options,
+        move |downloaded, total| totals_sink.lock().unwrap().push((downloaded, total)),
+        move |file| files_sink.lock().unwrap().push(file.clone()),
+    )
+    .await
+    .unwrap();
+
+    for name in MULTI_FILES {
+        assert_eq!(std::fs::read(dir.join(name)).unwrap(), body);
+    }
+    // All three files were requested before the stalled one was retried
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    let first: std::collections::HashSet<_> = requests[..3].iter().map(|r| &r.path).collect();
+    assert_eq!(first.len(), 3);
+
+    assert_eq!(*totals.lock().unwrap().last().unwrap(), (150_000, 150_000));
+    let files = files.lock().unwrap();
+    for name in MULTI_FILES {
+        let last = files.iter().rev().find(|f| f.filename == *name).unwrap();
+        assert_eq!((last.downloaded, last.total), (50_000, 50_000));
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Files already on disk count towards progress and are not fetched again.
+#[tokio::test]
+async fn parallel_skips_existing_files() {
+    let body = payload(10_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = multi_file_model(&server, MULTI_FILES, &sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_parallel_existing");
+    std::fs::write(dir.join(MULTI_FILES[0]), &body).unwrap();
+
+    let (log, on_progress) = progress_log();
+    download_model_files(&model, &dir, &no_retries(), on_progress, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+    let log = log.lock().unwrap();
+    assert_eq!(log[0], 10_000);
+    assert_eq!(*log.last().unwrap(), 30_000);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// After a file fails, no further files are started.
+#[tokio::test]
+async fn failed_file_stops_remaining_files() {
+    let server = TestServer::start(ServerOptions {
+        status_first: Some(404),
+        ..ServerOptions::new(payload(1000))
+    });
+    let model = multi_file_model(&server, MULTI_FILES, "");
+    let dir = fresh_dir("dikto_download_parallel_failure");
+    let options = DownloadOptions {
+        parallel_files: 1,
+        ..no_retries()
+    };
+
+    let err = download_model_files(&model, &dir, &options, |_, _| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ModelError::HttpStatus(404, _)), "got: {err}");
+    assert_eq!(server.requests().len(), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Repair
+// ---------------------------------------------------------------------------
+
+/// Repair re-downloads only the corrupt and missing files.
+#[tokio::test]
+async fn repair_fetches_only_bad_files() {
+    let body = payload(10_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = multi_file_model(&server, MULTI_FILES, &sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_repair");
+    std::fs::write(dir.join(MULTI_FILES[0]), &body).unwrap();
+    std::fs::write(dir.join(MULTI_FILES[1]), &body[..5_000]).unwrap();
+
+    let report = repair_model_files(&model, &dir, &no_retries(), |_, _| {})
+        .await
+        .unwrap();
+    assert!(report.is_ok(), "{report:?}");
+    assert!(report.extra.is_empty(), "{report:?}");
+    for name in MULTI_FILES {
+        assert_eq!(std::fs::read(dir.join(name)).unwrap(), body);
+    }
+    let mut paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["/decoder.onnx", "/encoder.onnx.data"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// An intact model needs no requests.
+#[tokio::test]
+async fn repair_intact_model_is_noop() {
+    let body = payload(1_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = multi_file_model(&server, MULTI_FILES, &sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_repair_noop");
+    for name in MULTI_FILES {
+        std::fs::write(dir.join(name), &body).unwrap();
+    }
+
+    let report = repair_model_files(&model, &dir, &no_retries(), |_, _| {})
+        .await
+        .unwrap();
+    assert!(report.is_ok());
+    assert!(server.requests().is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ---------------------------------------------------------------------------
+// Updates
+// ---------------------------------------------------------------------------
+
+/// A completed download records the installed hashes.
+#[tokio::test]
+async fn download_writes_install_record() {
+    let body = payload(5_000);
+    let server = TestServer::start(ServerOptions::new(body.clone()));
+    let model = model_for(server.url_for("/ggml-test.bin"), sha256_hex(&body));
+    let dir = fresh_dir("dikto_download_install_record");
+
+    download_model_files(&model, &dir, &no_retries(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    let record = read_install_record(&dir).unwrap();
+    assert_eq!(record["ggml-test.bin"], sha256_hex(&body));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Files recorded with an older hash are downloaded again; current ones are kept.
+#[tokio::test]
+async fn download_replaces_outdated_files() {
+    let old = payload(3_000);
+    let new = payload(4_000);
+    let server = TestServer::start(ServerOptions::new(new.clone()));
+    let dir = fresh_dir("dikto_download_update");
+
+    // Install the old version of the first two files
+    let old_model = multi_file_model(&server, &MULTI_FILES[..2], &sha256_hex(&old));
+    for name in &MULTI_FILES[..2] {
+        std::fs::write(dir.join(name), &old).unwrap();
+    }
+    download_model_files(&old_model, &dir, &no_retries(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    assert!(server.requests().is_empty());
+
+    // The new version changes both hashes and adds a file
+    let new_model = multi_file_model(&server, MULTI_FILES, &sha256_hex(&new));
+    download_model_files(&new_model, &dir, &no_retries(), |_, _| {}, |_| {})
+        .await
+        .unwrap();
+    for name in MULTI_FILES {
+        assert_eq!(std::fs::read(dir.join(name)).unwrap(), new);
+    }
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(read_install_record(&dir).unwrap().len(), 3);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -1,7 +1,10 @@
 // Tests for dikto_core::engine — hallucination detection, AsrSession buffer
-// accumulation, feed_samples, and buffer_duration_secs.
+// accumulation, feed_samples, buffer_duration_secs, and partial decode throttling.
 
-use dikto_core::engine::{is_hallucination, AsrSession};
+use dikto_core::engine::{is_hallucination, AsrSession, LoadedEngine, PartialThrottle};
+use dikto_core::transcribe::TranscribeError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 // ---------------------------------------------------------------------------
 // is_hallucination — bracket-style tokens
@@ -158,6 +161,60 @@ fn feed_samples_accumulates_buffer() {
     assert!((session.buffer_duration_secs() - 1.0).abs() < 0.01);
 }
 
+// ---------------------------------------------------------------------------
+// AsrSession — flushed audio
+// ---------------------------------------------------------------------------
+
+/// Without keep_flushed_audio, nothing is kept.
+#[test]
+fn flushed_audio_not_kept_by_default() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.feed_samples(&[0.1; 1600]);
+    let _ = session.flush(&engine);
+    assert!(session.take_flushed_audio().is_none());
+}
+
+/// The kept audio is exactly the buffer handed to inference (even if it fails).
+#[test]
+fn flushed_audio_is_flush_buffer() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.keep_flushed_audio(usize::MAX);
+    let samples: Vec<f32> = (0..1600).map(|i| i as f32 / 1600.0).collect();
+    session.feed_samples(&samples[..600]);
+    session.feed_samples(&samples[600..]);
+    assert!(matches!(
+        session.flush(&engine),
+        Err(TranscribeError::NotLoaded)
+    ));
+    assert_eq!(session.take_flushed_audio(), Some(samples));
+}
+
+/// Audio past the limit is not kept; later flushes add nothing.
+#[test]
+fn flushed_audio_is_capped() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.keep_flushed_audio(1000);
+    let samples: Vec<f32> = (0..1600).map(|i| i as f32 / 1600.0).collect();
+    session.feed_samples(&samples);
+    let _ = session.flush(&engine);
+    session.feed_samples(&[0.5; 1600]);
+    let _ = session.flush(&engine);
+    assert_eq!(session.take_flushed_audio(), Some(samples[..1000].to_vec()));
+}
+
+/// An empty flush adds nothing.
+#[test]
+fn flushed_audio_empty_flush() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.keep_flushed_audio(usize::MAX);
+    assert!(session.flush(&engine).unwrap().is_empty());
+    assert_eq!(session.take_flushed_audio(), Some(Vec::new()));
+}
+
 // ---------------------------------------------------------------------------
 // AsrSession — buffer_duration_secs
 // ---------------------------------------------------------------------------
@@ -184,3 +241,61 @@ fn buffer_duration_half_second() {
     session.feed_samples(&vec![0.0f32; 8000]);
     assert!((session.buffer_duration_secs() - 0.5).abs() < 0.01);
 }
+
+// ---------------------------------------------------------------------------
+// PartialThrottle
+// ---------------------------------------------------------------------------
+
+/// A fresh throttle should allow the first partial decode immediately.
+#[test]
+fn throttle_first_decode_is_due() {
+    let throttle = PartialThrottle::new(Duration::from_millis(500));
+    assert!(throttle.is_due(Instant::now()));
+}
+
+/// Fast inference should wait the minimum interval before the next decode.
+#[test]
+fn throttle_fast_inference_waits_min_interval() {
+    let mut throttle = PartialThrottle::new(Duration::from_millis(500));
+    let t0 = Instant::now();
+    throttle.record(t0, Duration::from_millis(50));
+    assert!(!throttle.is_due(t0 + Duration::from_millis(499)));
+    assert!(throttle.is_due(t0 + Duration::from_millis(500)));
+}
+
+/// Slow inference should back off proportionally so decoding never falls behind real time.
+#[test]
+fn throttle_slow_inference_backs_off() {
+    let mut throttle = PartialThrottle::new(Duration::from_millis(500));
+    let t0 = Instant::now();
+    throttle.record(t0, Duration::from_secs(1));
+    assert!(!throttle.is_due(t0 + Duration::from_millis(1999)));
+    assert!(throttle.is_due(t0 + Duration::from_secs(2)));
+}
+
+// ---------------------------------------------------------------------------
+// AsrSession — partial
+// ---------------------------------------------------------------------------
+
+/// With too little audio, partial should not touch the engine.
+#[test]
+fn partial_short_buffer_returns_none() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.feed_samples(&vec![0.0f32; 1600]);
+    assert!(session.partial(&engine).unwrap().is_none());
+}
+
+/// Once enough audio is buffered, partial should try to decode it.
+#[test]
+fn partial_without_engine_is_not_loaded() {
+    let engine: Arc<Mutex<Option<LoadedEngine>>> = Arc::new(Mutex::new(None));
+    let mut session = AsrSession::new("en".to_string());
+    session.feed_samples(&vec![0.0f32; 16000]);
+    assert!(matches!(
+        session.partial(&engine),
+        Err(TranscribeError::NotLoaded)
+    ));
+    // A failed partial decode must not consume the buffer
+    assert!((session.buffer_duration_secs() - 1.0).abs() < 0.01);
+}
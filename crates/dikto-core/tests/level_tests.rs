@@ -0,0 +1,101 @@
+// Tests for dikto_core::level — dBFS measurement of sample blocks and
+// throttling of level updates.
+
+use dikto_core::level::{to_dbfs, InputLevel, LevelMeter, MIN_DBFS};
+use std::time::{Duration, Instant};
+
+fn level(rms_dbfs: f32, peak_dbfs: f32, speech_probability: f32) -> InputLevel {
+    InputLevel {
+        rms_dbfs,
+        peak_dbfs,
+        speech_probability,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Measurement
+// ---------------------------------------------------------------------------
+
+/// Silence and empty blocks are floored at MIN_DBFS instead of -inf.
+#[test]
+fn test_silence_is_min_dbfs() {
+    let silent = InputLevel::measure(&[0.0; 512], 0.1);
+    assert_eq!(silent.rms_dbfs, MIN_DBFS);
+    assert_eq!(silent.peak_dbfs, MIN_DBFS);
+    assert_eq!(silent.speech_probability, 0.1);
+
+    let empty = InputLevel::measure(&[], 0.0);
+    assert_eq!(empty.rms_dbfs, MIN_DBFS);
+    assert_eq!(empty.peak_dbfs, MIN_DBFS);
+}
+
+/// A full-scale sine has its peak at 0 dBFS and its RMS at about -3 dBFS.
+#[test]
+fn test_full_scale_sine() {
+    let sine: Vec<f32> = (0..1600)
+        .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16000.0).sin())
+        .collect();
+    let measured = InputLevel::measure(&sine, 0.0);
+    assert!((measured.rms_dbfs + 3.01).abs() < 0.05, "{measured:?}");
+    assert!(measured.peak_dbfs.abs() < 0.01, "{measured:?}");
+}
+
+/// Negative samples count towards the peak.
+#[test]
+fn test_peak_uses_absolute_value() {
+    let measured = InputLevel::measure(&[0.1, -0.5, 0.2], 0.0);
+    assert!((measured.peak_dbfs - to_dbfs(0.5)).abs() < 1e-4);
+}
+
+/// Conversion from amplitude: halving is about -6 dB, tiny values are floored.
+#[test]
+fn test_to_dbfs() {
+    assert_eq!(to_dbfs(1.0), 0.0);
+    assert!((to_dbfs(0.5) + 6.02).abs() < 0.01);
+    assert_eq!(to_dbfs(1e-9), MIN_DBFS);
+    assert_eq!(to_dbfs(-1.0), MIN_DBFS);
+}
+
+// ---------------------------------------------------------------------------
+// Throttling
+// ---------------------------------------------------------------------------
+
+/// The first level is sent at once, later ones at most once per interval.
+#[test]
+fn test_meter_throttles_updates() {
+    let mut meter = LevelMeter::new(Duration::from_millis(50));
+    let start = Instant::now();
+
+    assert!(meter.update(level(-30.0, -20.0, 0.0), start).is_some());
+    assert!(meter
+        .update(level(-30.0, -20.0, 0.0), start + Duration::from_millis(32))
+        .is_none());
+    assert!(meter
+        .update(level(-30.0, -20.0, 0.0), start + Duration::from_millis(50))
+        .is_some());
+}
+
+/// Levels held back in between are merged, so a short peak isn't lost.
+#[test]
+fn test_meter_merges_skipped_levels() {
+    let mut meter = LevelMeter::new(Duration::from_millis(50));
+    let start = Instant::now();
+
+    meter.update(level(-40.0, -30.0, 0.1), start);
+    assert!(meter
+        .update(level(-10.0, -2.0, 0.2), start + Duration::from_millis(20))
+        .is_none());
+    assert!(meter
+        .update(level(-50.0, -45.0, 0.9), start + Duration::from_millis(40))
+        .is_none());
+    let sent = meter
+        .update(level(-45.0, -35.0, 0.3), start + Duration::from_millis(60))
+        .unwrap();
+    assert_eq!(sent, level(-10.0, -2.0, 0.9));
+
+    // The merged levels were consumed by that update
+    let next = meter
+        .update(level(-45.0, -35.0, 0.3), start + Duration::from_millis(120))
+        .unwrap();
+    assert_eq!(next, level(-45.0, -35.0, 0.3));
+}
@@ -51,6 +51,10 @@ fn listen_config_default() {
     assert_eq!(config.max_duration, 30);
     assert_eq!(config.silence_duration_ms, 1500);
     assert!((config.speech_threshold - 0.35).abs() < f32::EPSILON);
+    assert!(!config.continuous);
+    assert!(!config.save_audio);
+    assert_eq!(config.save_audio_max_files, 50);
+    assert_eq!(config.save_audio_max_age_days, 7);
 }
 
 /// ListenConfig::from(&DiktoConfig) should copy the relevant fields.
@@ -61,6 +65,9 @@ fn listen_config_from_dikto_config() {
         max_duration: 60,
         silence_duration_ms: 2000,
         speech_threshold: 0.5,
+        save_audio: true,
+        save_audio_max_files: 5,
+        save_audio_max_age_days: 1,
         ..DiktoConfig::default()
     };
     let listen_config = ListenConfig::from(&dikto_config);
@@ -68,6 +75,11 @@ fn listen_config_from_dikto_config() {
     assert_eq!(listen_config.max_duration, 60);
     assert_eq!(listen_config.silence_duration_ms, 2000);
     assert!((listen_config.speech_threshold - 0.5).abs() < f32::EPSILON);
+    assert!(listen_config.save_audio);
+    assert_eq!(listen_config.save_audio_max_files, 5);
+    assert_eq!(listen_config.save_audio_max_age_days, 1);
+    // Continuous mode is a per-session choice, not a config setting
+    assert!(!listen_config.continuous);
 }
 
 // ---------------------------------------------------------------------------
@@ -194,9 +206,11 @@ fn recording_state_processing() {
 fn recording_state_done_carries_text() {
     let state = RecordingState::Done {
         text: "hello".to_string(),
+        audio_path: Some("/tmp/session.wav".to_string()),
     };
-    if let RecordingState::Done { text } = &state {
+    if let RecordingState::Done { text, audio_path } = &state {
         assert_eq!(text, "hello");
+        assert_eq!(audio_path.as_deref(), Some("/tmp/session.wav"));
     } else {
         panic!("Expected Done variant");
     }
@@ -220,6 +234,7 @@ fn recording_state_error_carries_message() {
 fn recording_state_clone() {
     let state = RecordingState::Done {
         text: "hi".to_string(),
+        audio_path: None,
     };
     let cloned = state.clone();
     assert_eq!(state, cloned);
@@ -238,6 +253,7 @@ fn model_info_record_construction() {
         description: "A test model".to_string(),
         is_downloaded: false,
         backend: "Parakeet".to_string(),
+        update_available: false,
     };
     assert_eq!(record.name, "test-model");
     assert_eq!(record.size_mb, 100);
@@ -255,10 +271,12 @@ fn model_info_record_clone() {
         description: "d".to_string(),
         is_downloaded: true,
         backend: "Whisper".to_string(),
+        update_available: true,
     };
     let cloned = record.clone();
     assert_eq!(cloned.name, "m");
     assert!(cloned.is_downloaded);
+    assert!(cloned.update_available);
 }
 
 // ---------------------------------------------------------------------------
@@ -1,9 +1,17 @@
-// Tests for dikto_core::models — model registry, file lookup, path resolution,
-// URL validation, SHA-256 verification, and download/delete error paths.
+// Tests for dikto_core::models — model registry, user manifest parsing, file
+// lookup, path resolution, URL validation, SHA-256 verification, local
+// imports, installed-model verification, disk usage, update detection, and
+// download/delete error paths.
 
+mod common;
+
+use common::fresh_dir;
 use dikto_core::models::{
-    delete_model, find_model, is_model_downloaded, list_models, model_path, verify_file_sha256,
-    ModelBackend, ModelError, MODELS,
+    delete_model, delete_model_dir, dir_disk_usage, find_model, import_model, import_model_files,
+    is_installed_in, is_model_downloaded, list_models, load_manifest, mirror_url, model_disk_usage,
+    model_path, parse_manifest, read_install_record, update_available_in, verify_file_sha256,
+    verify_model, verify_model_files, ImportMode, ModelBackend, ModelError, ModelFile, ModelInfo,
+    INSTALL_RECORD, MODELS,
 };
 use std::collections::HashSet;
 
@@ -183,6 +191,21 @@ fn all_model_urls_are_https() {
     }
 }
 
+/// A mirror replaces scheme and host and keeps the path.
+#[test]
+fn mirror_url_replaces_host() {
+    let url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin";
+    assert_eq!(
+        mirror_url(url, "https://hf-mirror.com"),
+        "https://hf-mirror.com/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin"
+    );
+    // A trailing slash and a path prefix on the mirror are handled
+    assert_eq!(
+        mirror_url(url, "http://artifacts.corp:8081/hf/"),
+        "http://artifacts.corp:8081/hf/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Sizes
 // ---------------------------------------------------------------------------
@@ -295,11 +318,14 @@ fn is_model_downloaded_false_for_nonexistent() {
 // list_models
 // ---------------------------------------------------------------------------
 
-/// list_models should return all 6 registered models.
+/// list_models should return all 6 built-in models first (manifest models follow).
 #[test]
 fn list_models_returns_all() {
     let models = list_models();
-    assert_eq!(models.len(), 6);
+    assert!(models.len() >= 6);
+    for (listed, builtin) in models.iter().zip(MODELS) {
+        assert_eq!(listed.0.name, builtin.name);
+    }
 }
 
 /// list_models entries should have a consistent download status with is_model_downloaded.
@@ -349,3 +375,721 @@ fn model_error_download_failed_display() {
     let err = ModelError::DownloadFailed("timeout".to_string());
     assert!(err.to_string().contains("timeout"));
 }
+
+// ---------------------------------------------------------------------------
+// User model manifest
+// ---------------------------------------------------------------------------
+
+/// Parse a manifest and return the error message, for invalid-entry tests.
+fn manifest_error(json: &str) -> String {
+    match parse_manifest(json) {
+        Ok(_) => panic!("manifest should be rejected: {json}"),
+        Err(e) => {
+            assert!(matches!(e, ModelError::InvalidManifest(_)));
+            e.to_string()
+        }
+    }
+}
+
+/// A valid manifest entry becomes a ModelInfo with defaults filled in.
+#[test]
+fn manifest_valid_entry() {
+    let json = r#"{"models": [{
+        "name": "whisper-medium-q5",
+        "backend": "Whisper",
+        "description": "Quantized Whisper Medium",
+        "files": [{
+            "filename": "ggml-medium-q5_0.bin",
+            "url": "https://example.com/ggml-medium-q5_0.bin",
+            "size_mb": 539,
+            "sha256": "BE07E048E1E599AD46341C8D2A135645097A538221678B7ACDD1B1919C6E1B21"
+        }]
+    }]}"#;
+    let models = parse_manifest(json).unwrap();
+    assert_eq!(models.len(), 1);
+    let model = &models[0];
+    assert_eq!(model.name, "whisper-medium-q5");
+    assert_eq!(model.backend, ModelBackend::Whisper);
+    assert_eq!(model.size_mb, 539);
+    assert_eq!(model.files[0].filename, "ggml-medium-q5_0.bin");
+    // Hashes are normalized to lowercase
+    assert_eq!(
+        model.files[0].sha256,
+        "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21"
+    );
+}
+
+/// Multi-file Parakeet entries without hashes are accepted.
+#[test]
+fn manifest_parakeet_without_hashes() {
+    let json = r#"{"models": [{
+        "name": "parakeet-finetuned",
+        "backend": "parakeet",
+        "size_mb": 2600,
+        "files": [
+            {"filename": "encoder-model.onnx", "url": "http://models.local/encoder-model.onnx"},
+            {"filename": "decoder_joint-model.onnx", "url": "http://models.local/decoder_joint-model.onnx"},
+            {"filename": "vocab.txt", "url": "http://models.local/vocab.txt"}
+        ]
+    }]}"#;
+    let models = parse_manifest(json).unwrap();
+    assert_eq!(models[0].files.len(), 3);
+    assert_eq!(models[0].size_mb, 2600);
+    assert!(models[0].files[0].sha256.is_empty());
+}
+
+/// Parsing the same manifest again reuses the first parse's strings and
+/// file lists instead of leaking new copies on every reload.
+#[test]
+fn manifest_reload_reuses_models() {
+    let json = r#"{"models": [{
+        "name": "whisper-reload-test",
+        "backend": "whisper",
+        "files": [{"filename": "ggml-reload.bin", "url": "https://example.com/ggml-reload.bin"}]
+    }]}"#;
+    let first = parse_manifest(json).unwrap();
+    let second = parse_manifest(json).unwrap();
+    assert_eq!(first, second);
+    assert!(std::ptr::eq(first[0].name, second[0].name));
+    assert!(std::ptr::eq(first[0].files, second[0].files));
+}
+
+/// An empty model list is valid.
+#[test]
+fn manifest_empty_models() {
+    assert!(parse_manifest(r#"{"models": []}"#).unwrap().is_empty());
+}
+
+/// Malformed JSON or unknown fields are reported as invalid manifests.
+#[test]
+fn manifest_malformed_json() {
+    manifest_error("{not json");
+    let err = manifest_error(
+        r#"{"models": [{"name": "m", "backend": "whisper", "files": [], "sha": "x"}]}"#,
+    );
+    assert!(err.contains("sha"), "got: {err}");
+}
+
+/// Unknown backends are rejected with the entry's name in the message.
+#[test]
+fn manifest_unknown_backend() {
+    let err = manifest_error(
+        r#"{"models": [{"name": "m", "backend": "vosk",
+            "files": [{"filename": "m.bin", "url": "https://e.com/m.bin"}]}]}"#,
+    );
+    assert!(err.contains("'m'") && err.contains("vosk"), "got: {err}");
+}
+
+/// Custom models may not shadow built-in models.
+#[test]
+fn manifest_rejects_builtin_name() {
+    let err = manifest_error(
+        r#"{"models": [{"name": "whisper-tiny", "backend": "whisper",
+            "files": [{"filename": "ggml-tiny.bin", "url": "https://e.com/ggml-tiny.bin"}]}]}"#,
+    );
+    assert!(err.contains("built-in"), "got: {err}");
+}
+
+/// Duplicate model names are rejected.
+#[test]
+fn manifest_rejects_duplicate_names() {
+    let entry = r#"{"name": "dup", "backend": "whisper",
+        "files": [{"filename": "m.bin", "url": "https://e.com/m.bin"}]}"#;
+    let err = manifest_error(&format!(r#"{{"models": [{entry}, {entry}]}}"#));
+    assert!(
+        err.contains("model #2") && err.contains("duplicate"),
+        "got: {err}"
+    );
+}
+
+/// Names and filenames must not escape the models directory.
+#[test]
+fn manifest_rejects_path_traversal() {
+    manifest_error(
+        r#"{"models": [{"name": "../evil", "backend": "whisper",
+            "files": [{"filename": "m.bin", "url": "https://e.com/m.bin"}]}]}"#,
+    );
+    let err = manifest_error(
+        r#"{"models": [{"name": "ok", "backend": "whisper",
+            "files": [{"filename": "../../m.bin", "url": "https://e.com/m.bin"}]}]}"#,
+    );
+    assert!(err.contains("invalid filename"), "got: {err}");
+}
+
+/// URLs must be http(s) and hashes 64 hex digits.
+#[test]
+fn manifest_rejects_bad_url_and_hash() {
+    let err = manifest_error(
+        r#"{"models": [{"name": "m", "backend": "whisper",
+            "files": [{"filename": "m.bin", "url": "file:///tmp/m.bin"}]}]}"#,
+    );
+    assert!(err.contains("URL"), "got: {err}");
+    let err = manifest_error(
+        r#"{"models": [{"name": "m", "backend": "whisper",
+            "files": [{"filename": "m.bin", "url": "https://e.com/m.bin", "sha256": "abc"}]}]}"#,
+    );
+    assert!(err.contains("sha256"), "got: {err}");
+}
+
+/// Whisper entries need a ggml .bin file, and every entry needs files.
+#[test]
+fn manifest_rejects_missing_files() {
+    let err = manifest_error(r#"{"models": [{"name": "m", "backend": "parakeet", "files": []}]}"#);
+    assert!(err.contains("no files"), "got: {err}");
+    let err = manifest_error(
+        r#"{"models": [{"name": "m", "backend": "whisper",
+            "files": [{"filename": "model.onnx", "url": "https://e.com/model.onnx"}]}]}"#,
+    );
+    assert!(err.contains(".bin"), "got: {err}");
+}
+
+/// A missing manifest file means no custom models; a present one is parsed.
+#[test]
+fn load_manifest_from_file() {
+    let missing = std::env::temp_dir().join("dikto_manifest_missing_xyz.json");
+    assert!(load_manifest(&missing).unwrap().is_empty());
+
+    let path = std::env::temp_dir().join("dikto_manifest_test.json");
+    std::fs::write(
+        &path,
+        r#"{"models": [{"name": "from-file", "backend": "whisper",
+            "files": [{"filename": "m.bin", "url": "https://e.com/m.bin", "size_mb": 10}]}]}"#,
+    )
+    .unwrap();
+    let models = load_manifest(&path).unwrap();
+    assert_eq!(models[0].name, "from-file");
+    let _ = std::fs::remove_file(&path);
+}
+
+// ---------------------------------------------------------------------------
+// import_model
+// ---------------------------------------------------------------------------
+
+/// SHA-256 of "hello world".
+const HELLO_SHA256: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+/// Single-file model whose file must contain "hello world".
+const SINGLE_FILE_MODEL: ModelInfo = ModelInfo {
+    name: "import-test-single",
+    size_mb: 1,
+    description: "",
+    files: &[ModelFile {
+        filename: "ggml-test.bin",
+        url: "https://example.com/ggml-test.bin",
+        size_mb: 1,
+        sha256: HELLO_SHA256,
+    }],
+    backend: ModelBackend::Whisper,
+};
+
+/// Two-file model; only the first file has a known hash.
+const MULTI_FILE_MODEL: ModelInfo = ModelInfo {
+    name: "import-test-multi",
+    size_mb: 1,
+    description: "",
+    files: &[
+        ModelFile {
+            filename: "encoder-model.onnx",
+            url: "https://example.com/encoder-model.onnx",
+            size_mb: 1,
+            sha256: HELLO_SHA256,
+        },
+        ModelFile {
+            filename: "vocab.txt",
+            url: "https://example.com/vocab.txt",
+            size_mb: 0,
+            sha256: "",
+        },
+    ],
+    backend: ModelBackend::Parakeet,
+};
+
+/// A directory with all files is copied into the destination.
+#[test]
+fn import_directory_copies_files() {
+    let src = fresh_dir("dikto_import_dir_src");
+    std::fs::write(src.join("encoder-model.onnx"), b"hello world").unwrap();
+    std::fs::write(src.join("vocab.txt"), b"a\nb\n").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_dir_dest");
+    let _ = std::fs::remove_dir_all(&dest);
+
+    import_model_files(&MULTI_FILE_MODEL, &src, &dest, ImportMode::Copy).unwrap();
+    assert_eq!(
+        std::fs::read(dest.join("encoder-model.onnx")).unwrap(),
+        b"hello world"
+    );
+    let record = read_install_record(&dest).unwrap();
+    assert_eq!(record["encoder-model.onnx"], HELLO_SHA256);
+    assert!(dest.join("vocab.txt").is_file());
+    assert!(!dest.join("encoder-model.onnx.importing").exists());
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&dest);
+}
+
+/// A single-file model can be imported from the file itself, whatever its name.
+#[test]
+fn import_single_file() {
+    let src = fresh_dir("dikto_import_file_src");
+    let file = src.join("my-download.bin");
+    std::fs::write(&file, b"hello world").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_file_dest");
+    let _ = std::fs::remove_dir_all(&dest);
+
+    import_model_files(&SINGLE_FILE_MODEL, &file, &dest, ImportMode::Copy).unwrap();
+    assert!(dest.join("ggml-test.bin").is_file());
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&dest);
+}
+
+/// Symlink mode links to the source instead of copying it.
+#[cfg(unix)]
+#[test]
+fn import_symlink() {
+    let src = fresh_dir("dikto_import_link_src");
+    let file = src.join("ggml-test.bin");
+    std::fs::write(&file, b"hello world").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_link_dest");
+    let _ = std::fs::remove_dir_all(&dest);
+
+    import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Symlink).unwrap();
+    let link = dest.join("ggml-test.bin");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(std::fs::read(&link).unwrap(), b"hello world");
+
+    // Importing again replaces the existing link
+    import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Symlink).unwrap();
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&dest);
+}
+
+/// Importing a model from its own directory leaves the files untouched.
+#[test]
+fn import_from_own_directory_keeps_files() {
+    let dest = fresh_dir("dikto_import_same_dir");
+    std::fs::write(dest.join("ggml-test.bin"), b"hello world").unwrap();
+
+    for mode in [ImportMode::Copy, ImportMode::Symlink] {
+        import_model_files(&SINGLE_FILE_MODEL, &dest, &dest, mode).unwrap();
+        let file = dest.join("ggml-test.bin");
+        assert!(!file.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read(&file).unwrap(), b"hello world");
+        assert!(read_install_record(&dest).is_some());
+    }
+
+    let _ = std::fs::remove_dir_all(&dest);
+}
+
+/// Re-importing the target of an installed link as a copy replaces the link
+/// with a copy, without touching the target.
+#[cfg(unix)]
+#[test]
+fn import_copy_over_link_to_source() {
+    let src = fresh_dir("dikto_import_relink_src");
+    std::fs::write(src.join("ggml-test.bin"), b"hello world").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_relink_dest");
+    let _ = std::fs::remove_dir_all(&dest);
+
+    import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Symlink).unwrap();
+    import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Copy).unwrap();
+    let installed = dest.join("ggml-test.bin");
+    assert!(!installed
+        .symlink_metadata()
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(std::fs::read(&installed).unwrap(), b"hello world");
+    assert_eq!(
+        std::fs::read(src.join("ggml-test.bin")).unwrap(),
+        b"hello world"
+    );
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&dest);
+}
+
+/// A file with the wrong hash is rejected and nothing is installed.
+#[test]
+fn import_hash_mismatch_installs_nothing() {
+    let src = fresh_dir("dikto_import_bad_src");
+    std::fs::write(src.join("ggml-test.bin"), b"tampered").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_bad_dest");
+    let _ = std::fs::remove_dir_all(&dest);
+
+    let err = import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Copy).unwrap_err();
+    assert!(err.to_string().contains("SHA-256 mismatch"), "got: {err}");
+    assert!(!dest.exists());
+
+    let _ = std::fs::remove_dir_all(&src);
+}
+
+/// Missing files and multi-file models given a single file are rejected.
+#[test]
+fn import_missing_files_rejected() {
+    let src = fresh_dir("dikto_import_missing_src");
+    std::fs::write(src.join("encoder-model.onnx"), b"hello world").unwrap();
+    let dest = std::env::temp_dir().join("dikto_import_missing_dest");
+
+    let err = import_model_files(&MULTI_FILE_MODEL, &src, &dest, ImportMode::Copy).unwrap_err();
+    assert!(err.to_string().contains("vocab.txt"), "got: {err}");
+
+    let err = import_model_files(
+        &MULTI_FILE_MODEL,
+        &src.join("encoder-model.onnx"),
+        &dest,
+        ImportMode::Copy,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("directory"), "got: {err}");
+
+    let _ = std::fs::remove_dir_all(&src);
+}
+
+/// Importing an unknown model name fails with NotFound.
+#[test]
+fn import_unknown_model_not_found() {
+    let result = import_model(
+        "nonexistent-model-xyz",
+        std::path::Path::new("/tmp"),
+        ImportMode::Copy,
+    );
+    assert!(matches!(result, Err(ModelError::NotFound(_, _))));
+}
+
+// ---------------------------------------------------------------------------
+// verify_model
+// ---------------------------------------------------------------------------
+
+/// Intact files pass; files without a hash are listed as unverified.
+#[test]
+fn verify_intact_model() {
+    let dir = fresh_dir("dikto_verify_intact");
+    std::fs::write(dir.join("encoder-model.onnx"), b"hello world").unwrap();
+    std::fs::write(dir.join("vocab.txt"), b"a\nb\n").unwrap();
+
+    let report = verify_model_files(&MULTI_FILE_MODEL, &dir).unwrap();
+    assert!(report.is_ok());
+    assert!(report.corrupt.is_empty() && report.missing.is_empty() && report.extra.is_empty());
+    assert_eq!(report.unverified, vec!["vocab.txt"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Missing, corrupt and extra files are each reported.
+#[test]
+fn verify_reports_missing_corrupt_and_extra() {
+    let dir = fresh_dir("dikto_verify_broken");
+    std::fs::write(dir.join("encoder-model.onnx"), b"hello worl").unwrap();
+    std::fs::write(dir.join("notes.txt"), b"mine").unwrap();
+    std::fs::write(dir.join("vocab.txt.downloading"), b"a").unwrap();
+
+    let report = verify_model_files(&MULTI_FILE_MODEL, &dir).unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(report.corrupt, vec!["encoder-model.onnx"]);
+    assert_eq!(report.missing, vec!["vocab.txt"]);
+    assert_eq!(report.extra, vec!["notes.txt", "vocab.txt.downloading"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A file without a hash that is far smaller than expected counts as corrupt.
+#[test]
+fn verify_truncated_file_without_hash() {
+    let model = ModelInfo {
+        files: &[ModelFile {
+            filename: "vocab.txt",
+            url: "https://example.com/vocab.txt",
+            size_mb: 1,
+            sha256: "",
+        }],
+        ..MULTI_FILE_MODEL
+    };
+    let dir = fresh_dir("dikto_verify_truncated");
+    std::fs::write(dir.join("vocab.txt"), b"a").unwrap();
+
+    let report = verify_model_files(&model, &dir).unwrap();
+    assert_eq!(report.corrupt, vec!["vocab.txt"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A model that was never installed reports every file as missing.
+#[test]
+fn verify_uninstalled_model() {
+    let dir = std::env::temp_dir().join("dikto_verify_uninstalled");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let report = verify_model_files(&MULTI_FILE_MODEL, &dir).unwrap();
+    assert_eq!(report.missing, vec!["encoder-model.onnx", "vocab.txt"]);
+}
+
+/// Unknown model names are rejected.
+#[test]
+fn verify_unknown_model() {
+    assert!(matches!(
+        verify_model("nonexistent-model"),
+        Err(ModelError::NotFound(..))
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Disk usage
+// ---------------------------------------------------------------------------
+
+/// Disk usage sums all files and lists partial downloads separately.
+#[test]
+fn disk_usage_counts_partial_files() {
+    let dir = fresh_dir("dikto_disk_usage");
+    std::fs::write(dir.join("encoder-model.onnx"), vec![0u8; 1000]).unwrap();
+    std::fs::write(dir.join("vocab.txt.downloading"), vec![0u8; 300]).unwrap();
+    std::fs::write(dir.join("decoder.onnx.importing"), vec![0u8; 20]).unwrap();
+
+    let usage = dir_disk_usage(&dir).unwrap();
+    assert_eq!(usage.total_bytes, 1320);
+    assert_eq!(usage.partial_bytes, 320);
+    assert_eq!(
+        usage.partial_files,
+        vec!["decoder.onnx.importing", "vocab.txt.downloading"]
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Linked files count toward the total and separately as linked bytes,
+/// since deleting the model doesn't free them.
+#[cfg(unix)]
+#[test]
+fn disk_usage_counts_linked_files() {
+    let src = fresh_dir("dikto_disk_usage_link_src");
+    std::fs::write(src.join("ggml-test.bin"), vec![0u8; 500]).unwrap();
+    let dir = fresh_dir("dikto_disk_usage_link");
+    std::fs::write(dir.join("vocab.txt"), vec![0u8; 100]).unwrap();
+    std::os::unix::fs::symlink(src.join("ggml-test.bin"), dir.join("ggml-test.bin")).unwrap();
+
+    let usage = dir_disk_usage(&dir).unwrap();
+    assert_eq!(usage.total_bytes, 600);
+    assert_eq!(usage.linked_bytes, 500);
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A directory that doesn't exist uses no space.
+#[test]
+fn disk_usage_of_missing_dir_is_zero() {
+    let dir = std::env::temp_dir().join("dikto_disk_usage_missing");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(dir_disk_usage(&dir).unwrap().total_bytes, 0);
+}
+
+/// Disk usage of an unknown model is an error.
+#[test]
+fn disk_usage_unknown_model() {
+    assert!(matches!(
+        model_disk_usage("nonexistent-model"),
+        Err(ModelError::NotFound(..))
+    ));
+}
+
+/// Directories of models that are no longer known can be deleted too.
+#[test]
+fn delete_model_dir_removes_unknown_model() {
+    let models = fresh_dir("dikto_delete_model_dir");
+    let orphan = models.join("retired-model");
+    std::fs::create_dir_all(&orphan).unwrap();
+    std::fs::write(orphan.join("model.bin"), b"old").unwrap();
+
+    assert!(delete_model_dir(&models, "retired-model").unwrap());
+    assert!(!orphan.exists());
+    // Nothing left to delete
+    assert!(!delete_model_dir(&models, "retired-model").unwrap());
+
+    let _ = std::fs::remove_dir_all(&models);
+}
+
+/// Names that aren't plain directory names are rejected, so nothing outside
+/// the models directory can be deleted.
+#[test]
+fn delete_model_dir_rejects_paths() {
+    let models = fresh_dir("dikto_delete_model_dir_paths");
+    std::fs::create_dir_all(models.join("inner")).unwrap();
+    for name in ["..", ".", "", "inner/..", "../dikto_delete_model_dir_paths"] {
+        assert!(
+            matches!(
+                delete_model_dir(&models, name),
+                Err(ModelError::NotFound(..))
+            ),
+            "{name}"
+        );
+    }
+    assert!(models.join("inner").exists());
+
+    let _ = std::fs::remove_dir_all(&models);
+}
+
+/// Deleting a linked import removes the link, not the file it points to.
+#[cfg(unix)]
+#[test]
+fn delete_model_dir_keeps_link_targets() {
+    let src = fresh_dir("dikto_delete_link_src");
+    std::fs::write(src.join("ggml-test.bin"), b"hello world").unwrap();
+    let models = fresh_dir("dikto_delete_link_models");
+    let dest = models.join("test-model");
+    import_model_files(&SINGLE_FILE_MODEL, &src, &dest, ImportMode::Symlink).unwrap();
+
+    assert!(delete_model_dir(&models, "test-model").unwrap());
+    assert!(!dest.exists());
+    assert_eq!(
+        std::fs::read(src.join("ggml-test.bin")).unwrap(),
+        b"hello world"
+    );
+
+    let _ = std::fs::remove_dir_all(&src);
+    let _ = std::fs::remove_dir_all(&models);
+}
+
+// ---------------------------------------------------------------------------
+// Update detection
+// ---------------------------------------------------------------------------
+
+/// MULTI_FILE_MODEL with a different hash for its encoder.
+const UPDATED_MULTI_FILE_MODEL: ModelInfo = ModelInfo {
+    files: &[
+        ModelFile {
+            filename: "encoder-model.onnx",
+            url: "https://example.com/encoder-model.onnx",
+            size_mb: 1,
+            sha256: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        },
+        ModelFile {
+            filename: "vocab.txt",
+            url: "https://example.com/vocab.txt",
+            size_mb: 0,
+            sha256: "",
+        },
+    ],
+    ..MULTI_FILE_MODEL
+};
+
+/// MULTI_FILE_MODEL with a file added in a newer version.
+const EXTENDED_MULTI_FILE_MODEL: ModelInfo = ModelInfo {
+    files: &[
+        ModelFile {
+            filename: "encoder-model.onnx",
+            url: "https://example.com/encoder-model.onnx",
+            size_mb: 1,
+            sha256: HELLO_SHA256,
+        },
+        ModelFile {
+            filename: "vocab.txt",
+            url: "https://example.com/vocab.txt",
+            size_mb: 0,
+            sha256: "",
+        },
+        ModelFile {
+            filename: "decoder_joint-model.onnx",
+            url: "https://example.com/decoder_joint-model.onnx",
+            size_mb: 1,
+            sha256: HELLO_SHA256,
+        },
+    ],
+    ..MULTI_FILE_MODEL
+};
+
+/// Installed model files of MULTI_FILE_MODEL, with or without an install record.
+fn installed_dir(name: &str, with_record: bool) -> std::path::PathBuf {
+    let src = fresh_dir(&format!("{name}_src"));
+    std::fs::write(src.join("encoder-model.onnx"), b"hello world").unwrap();
+    std::fs::write(src.join("vocab.txt"), b"a").unwrap();
+    let dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    import_model_files(&MULTI_FILE_MODEL, &src, &dir, ImportMode::Copy).unwrap();
+    if !with_record {
+        std::fs::remove_file(dir.join(INSTALL_RECORD)).unwrap();
+    }
+    let _ = std::fs::remove_dir_all(&src);
+    dir
+}
+
+/// An install matching the current definition has no update.
+#[test]
+fn no_update_for_current_install() {
+    let dir = installed_dir("dikto_update_current", true);
+    assert!(!update_available_in(&MULTI_FILE_MODEL, &dir, None));
+    // The install record is not an extra file
+    assert!(verify_model_files(&MULTI_FILE_MODEL, &dir)
+        .unwrap()
+        .extra
+        .is_empty());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A changed hash in the definition is an update for the recorded install.
+#[test]
+fn update_for_changed_hash() {
+    let dir = installed_dir("dikto_update_changed", true);
+    assert!(update_available_in(&UPDATED_MULTI_FILE_MODEL, &dir, None));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Without an install record, the files are compared against the fallback definition.
+#[test]
+fn update_without_record_uses_fallback() {
+    let dir = installed_dir("dikto_update_fallback", false);
+    assert!(update_available_in(
+        &UPDATED_MULTI_FILE_MODEL,
+        &dir,
+        Some(&MULTI_FILE_MODEL)
+    ));
+    assert!(!update_available_in(
+        &MULTI_FILE_MODEL,
+        &dir,
+        Some(&MULTI_FILE_MODEL)
+    ));
+    // Nothing to compare against: no update
+    assert!(!update_available_in(&UPDATED_MULTI_FILE_MODEL, &dir, None));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A file added in a newer version is an update; the installed version
+/// still counts as installed.
+#[test]
+fn added_file_is_update_not_missing_install() {
+    for with_record in [true, false] {
+        let dir = installed_dir("dikto_update_added_file", with_record);
+        assert!(is_installed_in(&dir, &MULTI_FILE_MODEL));
+        assert!(update_available_in(
+            &EXTENDED_MULTI_FILE_MODEL,
+            &dir,
+            Some(&MULTI_FILE_MODEL)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
+
+/// An install missing one of its files is not installed.
+#[test]
+fn missing_file_is_not_installed() {
+    for with_record in [true, false] {
+        let dir = installed_dir("dikto_installed_missing_file", with_record);
+        std::fs::remove_file(dir.join("vocab.txt")).unwrap();
+        assert!(!is_installed_in(&dir, &MULTI_FILE_MODEL));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    let empty = fresh_dir("dikto_installed_empty");
+    assert!(!is_installed_in(&empty, &MULTI_FILE_MODEL));
+}
+
+/// A model that isn't installed has no update.
+#[test]
+fn no_update_when_not_installed() {
+    let dir = std::env::temp_dir().join("dikto_update_not_installed");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(!update_available_in(
+        &UPDATED_MULTI_FILE_MODEL,
+        &dir,
+        Some(&MULTI_FILE_MODEL)
+    ));
+}
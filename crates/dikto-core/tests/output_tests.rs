@@ -0,0 +1,201 @@
+// Tests for dikto_core::output — subtitle cue building, line wrapping,
+// timestamp formatting, and SRT / WebVTT / JSON / text rendering.
+
+mod common;
+
+use common::segment;
+use dikto_core::output::{
+    build_cues, format_timestamp, format_transcript, to_json, to_srt, to_vtt, wrap_text,
+    OutputFormat, SubtitleOptions,
+};
+use dikto_core::transcribe::TranscriptSegment;
+
+// ---------------------------------------------------------------------------
+// format_timestamp
+// ---------------------------------------------------------------------------
+
+/// SRT timestamps use a comma before the milliseconds.
+#[test]
+fn timestamp_srt_style() {
+    assert_eq!(format_timestamp(3725.5, ','), "01:02:05,500");
+}
+
+/// WebVTT timestamps use a dot and round to the nearest millisecond.
+#[test]
+fn timestamp_vtt_style_rounds() {
+    assert_eq!(format_timestamp(1.2346, '.'), "00:00:01.235");
+}
+
+/// Negative times should clamp to zero.
+#[test]
+fn timestamp_negative_clamps() {
+    assert_eq!(format_timestamp(-1.0, ','), "00:00:00,000");
+}
+
+// ---------------------------------------------------------------------------
+// wrap_text
+// ---------------------------------------------------------------------------
+
+/// Text should wrap at word boundaries within the line length.
+#[test]
+fn wrap_at_word_boundaries() {
+    assert_eq!(
+        wrap_text("the quick brown fox jumps", 10),
+        vec!["the quick", "brown fox", "jumps"]
+    );
+}
+
+/// A word longer than the limit should get its own line rather than be split.
+#[test]
+fn wrap_long_word_own_line() {
+    assert_eq!(
+        wrap_text("a extraordinarily b", 5),
+        vec!["a", "extraordinarily", "b"]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// build_cues
+// ---------------------------------------------------------------------------
+
+/// A short segment should become a single cue with its timing.
+#[test]
+fn cues_short_segment_single_cue() {
+    let segments = vec![segment(&[("Hello", 0.5, 0.9), ("world.", 1.0, 1.4)])];
+    let cues = build_cues(&segments, &SubtitleOptions::default());
+    assert_eq!(cues.len(), 1);
+    assert_eq!(cues[0].text, "Hello world.");
+    assert!((cues[0].start - 0.5).abs() < 1e-9);
+    assert!((cues[0].end - 1.4).abs() < 1e-9);
+}
+
+/// Cues should be split when they would exceed the max cue duration.
+#[test]
+fn cues_split_by_duration() {
+    let words: Vec<(&str, f64, f64)> = (0..10)
+        .map(|i| ("word", i as f64, i as f64 + 0.9))
+        .collect();
+    let options = SubtitleOptions {
+        max_line_length: 100,
+        max_cue_duration_secs: 3.0,
+    };
+    let cues = build_cues(&[segment(&words)], &options);
+    assert!(cues.len() >= 4, "got {} cues", cues.len());
+    for cue in &cues {
+        assert!(cue.end - cue.start <= 3.0 + 1e-9);
+    }
+}
+
+/// Cues should be split so that no cue has more than two wrapped lines.
+#[test]
+fn cues_split_by_line_count() {
+    let words: Vec<(&str, f64, f64)> = (0..12)
+        .map(|i| ("abcd", i as f64 * 0.1, i as f64 * 0.1 + 0.1))
+        .collect();
+    let options = SubtitleOptions {
+        max_line_length: 10,
+        max_cue_duration_secs: 60.0,
+    };
+    let cues = build_cues(&[segment(&words)], &options);
+    for cue in &cues {
+        assert!(
+            cue.text.lines().count() <= 2,
+            "cue too tall: {:?}",
+            cue.text
+        );
+        assert!(cue.text.lines().all(|l| l.len() <= 10));
+    }
+    let total_words: usize = cues.iter().map(|c| c.text.split_whitespace().count()).sum();
+    assert_eq!(total_words, 12);
+}
+
+/// Segments without word timings should be split with interpolated times.
+#[test]
+fn cues_untimed_segment_interpolates() {
+    let seg = TranscriptSegment {
+        text: "one two three four".to_string(),
+        is_final: true,
+        start: 0.0,
+        end: 8.0,
+        words: Vec::new(),
+    };
+    let options = SubtitleOptions {
+        max_line_length: 42,
+        max_cue_duration_secs: 4.0,
+    };
+    let cues = build_cues(&[seg], &options);
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].text, "one two");
+    assert!((cues[1].start - 4.0).abs() < 1e-9);
+    assert!((cues[1].end - 8.0).abs() < 1e-9);
+}
+
+// ---------------------------------------------------------------------------
+// Renderers
+// ---------------------------------------------------------------------------
+
+/// SRT output should number cues and use comma timestamps.
+#[test]
+fn srt_output() {
+    let segments = vec![
+        segment(&[("Hello.", 0.0, 1.0)]),
+        segment(&[("Bye.", 2.0, 2.5)]),
+    ];
+    let srt = to_srt(&segments, &SubtitleOptions::default());
+    assert_eq!(
+        srt,
+        "1\n00:00:00,000 --> 00:00:01,000\nHello.\n\n2\n00:00:02,000 --> 00:00:02,500\nBye.\n\n"
+    );
+}
+
+/// WebVTT output should start with the WEBVTT header and use dot timestamps.
+#[test]
+fn vtt_output() {
+    let segments = vec![segment(&[("Hello.", 0.0, 1.0)])];
+    let vtt = to_vtt(&segments, &SubtitleOptions::default());
+    assert_eq!(vtt, "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\nHello.\n\n");
+}
+
+/// JSON output should include text, language, model, segments and words.
+#[test]
+fn json_output() {
+    let segments = vec![segment(&[("Hello", 0.0, 0.4), ("world.", 0.5, 1.0)])];
+    let json = to_json(&segments, "en", "parakeet-tdt-0.6b-v2");
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["text"], "Hello world.");
+    assert_eq!(value["language"], "en");
+    assert_eq!(value["model"], "parakeet-tdt-0.6b-v2");
+    assert_eq!(value["segments"][0]["end"], 1.0);
+    assert_eq!(value["segments"][0]["words"][1]["text"], "world.");
+}
+
+/// Text format should join segment texts with spaces.
+#[test]
+fn format_transcript_text() {
+    let segments = vec![
+        segment(&[("Hi.", 0.0, 0.5)]),
+        segment(&[("Bye.", 1.0, 1.5)]),
+    ];
+    let text = format_transcript(
+        &segments,
+        OutputFormat::Text,
+        "en",
+        "m",
+        &SubtitleOptions::default(),
+    );
+    assert_eq!(text, "Hi. Bye.");
+}
+
+// ---------------------------------------------------------------------------
+// OutputFormat parsing
+// ---------------------------------------------------------------------------
+
+/// Format names should parse case-insensitively, with aliases.
+#[test]
+fn output_format_from_str() {
+    assert_eq!("SRT".parse::<OutputFormat>(), Ok(OutputFormat::Srt));
+    assert_eq!("webvtt".parse::<OutputFormat>(), Ok(OutputFormat::Vtt));
+    assert_eq!("txt".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+    assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+    assert!("docx".parse::<OutputFormat>().is_err());
+}
@@ -0,0 +1,128 @@
+// Tests for dikto_core::recordings — writing session audio to WAV and
+// pruning old recordings by count and age.
+
+use dikto_core::decode::decode_file;
+use dikto_core::recordings::{list_recordings, prune_recordings, save_recording};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dikto_recordings_{name}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Create an (empty) file in `dir` last modified at `modified`.
+fn touch(dir: &Path, name: &str, modified: SystemTime) -> PathBuf {
+    std::fs::create_dir_all(dir).unwrap();
+    let path = dir.join(name);
+    File::create(&path).unwrap().set_modified(modified).unwrap();
+    path
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+// ---------------------------------------------------------------------------
+// Saving
+// ---------------------------------------------------------------------------
+
+/// The saved file decodes back to exactly the same samples.
+#[test]
+fn test_save_roundtrip_is_exact() {
+    let dir = temp_dir("roundtrip");
+    let samples: Vec<f32> = (0..16000)
+        .map(|i| (i as f32 * 0.01).sin() * 0.3 + 1e-7 * i as f32)
+        .collect();
+
+    let path = save_recording(&dir, &samples).unwrap();
+    assert!(path.starts_with(&dir));
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(
+        name.starts_with("session-") && name.ends_with(".wav"),
+        "{name}"
+    );
+
+    let reader = hound::WavReader::open(&path).unwrap();
+    let spec = reader.spec();
+    assert_eq!(spec.channels, 1);
+    assert_eq!(spec.sample_rate, 16000);
+    assert_eq!(spec.sample_format, hound::SampleFormat::Float);
+    assert_eq!(decode_file(&path).unwrap(), samples);
+}
+
+/// Sessions saved at the same moment get distinct files.
+#[test]
+fn test_save_never_overwrites() {
+    let dir = temp_dir("unique");
+    let paths: Vec<PathBuf> = (0..5)
+        .map(|i| save_recording(&dir, &[i as f32 * 0.1; 160]).unwrap())
+        .collect();
+    for (i, path) in paths.iter().enumerate() {
+        let samples = decode_file(path).unwrap();
+        assert_eq!(samples, vec![i as f32 * 0.1; 160]);
+    }
+    assert_eq!(list_recordings(&dir).unwrap().len(), 5);
+}
+
+// ---------------------------------------------------------------------------
+// Pruning
+// ---------------------------------------------------------------------------
+
+/// Only the newest `max_files` recordings are kept; other files are untouched.
+#[test]
+fn test_prune_by_count() {
+    let dir = temp_dir("count");
+    let now = SystemTime::now();
+    for (i, age) in [4u32, 1, 3, 2].iter().enumerate() {
+        touch(&dir, &format!("session-{i}.wav"), now - *age * DAY / 10);
+    }
+    touch(&dir, "notes.txt", now - 30 * DAY);
+    touch(&dir, "other.wav", now - 30 * DAY);
+
+    let deleted = prune_recordings(&dir, 2, 0, now).unwrap();
+    assert_eq!(deleted, 2);
+    assert_eq!(
+        names(&dir),
+        ["notes.txt", "other.wav", "session-1.wav", "session-3.wav"]
+    );
+}
+
+/// Recordings older than `max_age_days` are deleted.
+#[test]
+fn test_prune_by_age() {
+    let dir = temp_dir("age");
+    let now = SystemTime::now();
+    touch(&dir, "session-old.wav", now - 8 * DAY);
+    touch(&dir, "session-recent.wav", now - 6 * DAY);
+    touch(&dir, "session-new.wav", now);
+
+    let deleted = prune_recordings(&dir, 0, 7, now).unwrap();
+    assert_eq!(deleted, 1);
+    assert_eq!(names(&dir), ["session-new.wav", "session-recent.wav"]);
+}
+
+/// Zero limits keep everything; a missing directory is not an error.
+#[test]
+fn test_prune_unlimited_and_missing_dir() {
+    let dir = temp_dir("unlimited");
+    let now = SystemTime::now();
+    for i in 0..3 {
+        touch(&dir, &format!("session-{i}.wav"), now - 400 * DAY);
+    }
+    assert_eq!(prune_recordings(&dir, 0, 0, now).unwrap(), 0);
+    assert_eq!(names(&dir).len(), 3);
+
+    let missing = temp_dir("missing");
+    assert_eq!(prune_recordings(&missing, 1, 1, now).unwrap(), 0);
+    assert!(list_recordings(&missing).unwrap().is_empty());
+}
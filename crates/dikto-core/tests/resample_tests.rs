@@ -0,0 +1,163 @@
+// Tests for dikto_core::resample — output length and timing, streaming in
+// blocks, passband flatness, and rejection of aliases and images, measured
+// on synthetic tones.
+
+use dikto_core::resample::{resample, ResampleQuality, Resampler};
+
+const QUALITIES: [ResampleQuality; 3] = [
+    ResampleQuality::Fast,
+    ResampleQuality::Balanced,
+    ResampleQuality::Best,
+];
+
+/// `secs` of a unit-amplitude sine at `freq` Hz, sampled at `rate`.
+fn tone(freq: f64, rate: u32, secs: f64) -> Vec<f32> {
+    let len = (rate as f64 * secs) as usize;
+    (0..len)
+        .map(|i| (2.0 * std::f64::consts::PI * freq * i as f64 / rate as f64).sin() as f32)
+        .collect()
+}
+
+/// Amplitude of the `freq` Hz component of `samples` (at `rate`), over the
+/// middle half of the signal to skip the filter's edges.
+fn amplitude_at(samples: &[f32], rate: u32, freq: f64) -> f64 {
+    let middle = &samples[samples.len() / 4..samples.len() * 3 / 4];
+    let (mut re, mut im) = (0.0f64, 0.0f64);
+    for (i, &x) in middle.iter().enumerate() {
+        let phase = 2.0 * std::f64::consts::PI * freq * i as f64 / rate as f64;
+        re += x as f64 * phase.cos();
+        im += x as f64 * phase.sin();
+    }
+    2.0 * (re * re + im * im).sqrt() / middle.len() as f64
+}
+
+/// RMS level of the middle half of `samples`, in dB relative to a unit sine.
+fn level_db(samples: &[f32]) -> f64 {
+    let middle = &samples[samples.len() / 4..samples.len() * 3 / 4];
+    let power: f64 = middle.iter().map(|&x| (x as f64).powi(2)).sum::<f64>() / middle.len() as f64;
+    10.0 * (power / 0.5).log10()
+}
+
+// ---------------------------------------------------------------------------
+// Length, timing and streaming
+// ---------------------------------------------------------------------------
+
+/// Equal rates pass samples through unchanged.
+#[test]
+fn same_rate_is_passthrough() {
+    let input = tone(440.0, 16000, 0.1);
+    assert_eq!(resample(&input, 16000, 16000, ResampleQuality::Best), input);
+}
+
+/// One second of input gives one second of output for common device rates.
+#[test]
+fn output_length_matches_duration() {
+    for rate in [8000, 22050, 44100, 48000, 96000] {
+        let input = vec![0.0; rate as usize];
+        let output = resample(&input, rate, 16000, ResampleQuality::Balanced);
+        assert_eq!(output.len(), 16000, "from {rate} Hz");
+    }
+}
+
+/// The filter is centered: a resampled tone lines up with the same tone
+/// sampled directly at the output rate (no delay).
+#[test]
+fn output_is_not_delayed() {
+    let input = tone(1000.0, 44100, 0.5);
+    let output = resample(&input, 44100, 16000, ResampleQuality::Balanced);
+    let expected = tone(1000.0, 16000, 0.5);
+    let max_error = output[2000..6000]
+        .iter()
+        .zip(&expected[2000..6000])
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0f32, f32::max);
+    assert!(max_error < 1e-3, "max error {max_error}");
+}
+
+/// Feeding the input in uneven blocks gives the same result as all at once.
+#[test]
+fn streaming_matches_one_shot() {
+    let input = tone(1234.0, 48000, 0.3);
+    let expected = resample(&input, 48000, 16000, ResampleQuality::Balanced);
+
+    let mut resampler = Resampler::new(48000, 16000, ResampleQuality::Balanced);
+    let mut output = Vec::new();
+    let mut rest = &input[..];
+    for size in [1, 7, 480, 1024, 3, 512].iter().cycle() {
+        if rest.is_empty() {
+            break;
+        }
+        let (block, tail) = rest.split_at((*size).min(rest.len()));
+        resampler.process(block, &mut output);
+        rest = tail;
+    }
+    resampler.finish(&mut output);
+    assert_eq!(output, expected);
+}
+
+/// A constant signal keeps its level (unity DC gain for every filter phase).
+#[test]
+fn dc_level_is_preserved() {
+    for quality in QUALITIES {
+        let output = resample(&vec![0.5; 44100], 44100, 16000, quality);
+        let middle = &output[4000..12000];
+        assert!(middle.iter().all(|x| (x - 0.5).abs() < 1e-4), "{quality:?}");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Frequency response
+// ---------------------------------------------------------------------------
+
+/// Speech-band tones pass with less than 0.1 dB of gain error.
+#[test]
+fn passband_is_flat() {
+    for quality in QUALITIES {
+        for freq in [100.0, 1000.0, 3000.0, 5000.0] {
+            let output = resample(&tone(freq, 48000, 1.0), 48000, 16000, quality);
+            let gain_db = 20.0 * amplitude_at(&output, 16000, freq).log10();
+            assert!(
+                gain_db.abs() < 0.1,
+                "{quality:?} at {freq} Hz: {gain_db:.3} dB"
+            );
+        }
+    }
+}
+
+/// Tones above the output Nyquist frequency are removed instead of folding
+/// back into the speech band.
+#[test]
+fn aliasing_is_rejected() {
+    let limits = [
+        (ResampleQuality::Fast, -60.0),
+        (ResampleQuality::Balanced, -85.0),
+        (ResampleQuality::Best, -100.0),
+    ];
+    for (quality, limit) in limits {
+        // 12 kHz at 48 kHz would alias to 4 kHz; 9 kHz at 44.1 kHz to 7 kHz
+        for (freq, rate) in [(12000.0, 48000), (9000.0, 44100), (20000.0, 44100)] {
+            let output = resample(&tone(freq, rate, 1.0), rate, 16000, quality);
+            let level = level_db(&output);
+            assert!(
+                level < limit,
+                "{quality:?}: {freq} Hz from {rate} Hz leaks at {level:.1} dB"
+            );
+        }
+    }
+}
+
+/// Upsampling doesn't create images of the input above its Nyquist frequency.
+#[test]
+fn upsampling_rejects_images() {
+    let output = resample(
+        &tone(1000.0, 8000, 1.0),
+        8000,
+        16000,
+        ResampleQuality::Balanced,
+    );
+    let wanted = amplitude_at(&output, 16000, 1000.0);
+    assert!((20.0 * wanted.log10()).abs() < 0.1);
+    // The image of 1 kHz around the old 8 kHz rate
+    let image = amplitude_at(&output, 16000, 7000.0);
+    assert!(20.0 * image.log10() < -80.0, "image at {image}");
+}
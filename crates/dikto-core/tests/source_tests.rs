@@ -0,0 +1,222 @@
+// Tests for dikto_core::source — AudioSource implementations for in-memory
+// samples, decoded files, raw PCM readers and channels, AudioInput opening,
+// and the throttled input overflow warnings.
+
+use dikto_core::audio::AudioError;
+use dikto_core::source::{
+    AudioInput, AudioSource, ChannelSource, FileSource, MemorySource, OverflowWarnings, PcmDecoder,
+    PcmFormat, PcmReaderSource, OVERFLOW_WARNING_INTERVAL,
+};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+/// Drain a source until it reports finished, failing after a few seconds.
+fn read_all(source: &mut dyn AudioSource) -> Vec<f32> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut out = Vec::new();
+    loop {
+        let samples = source.read_samples();
+        if samples.is_empty() {
+            if source.is_finished() {
+                return out;
+            }
+            assert!(Instant::now() < deadline, "source never finished");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        out.extend_from_slice(&samples);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MemorySource
+// ---------------------------------------------------------------------------
+
+/// Samples should be handed out in 100ms chunks until exhausted.
+#[test]
+fn memory_source_chunks_and_finishes() {
+    let mut source = MemorySource::new(vec![0.5; 4000]);
+    assert!(!source.is_finished());
+    assert_eq!(source.read_samples().len(), 1600);
+    assert_eq!(source.read_samples().len(), 1600);
+    assert_eq!(source.read_samples().len(), 800);
+    assert!(source.is_finished());
+    assert!(source.read_samples().is_empty());
+}
+
+/// stop() should end the source immediately.
+#[test]
+fn memory_source_stop_finishes() {
+    let mut source = MemorySource::new(vec![0.0; 16000]);
+    source.stop();
+    assert!(source.is_finished());
+    assert!(source.read_samples().is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// PcmReaderSource
+// ---------------------------------------------------------------------------
+
+/// s16le bytes should be converted to normalized f32 samples.
+#[test]
+fn pcm_reader_s16le() {
+    let bytes: Vec<u8> = [0i16, i16::MAX, -i16::MAX]
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    let mut source = PcmReaderSource::new(Cursor::new(bytes), PcmFormat::S16Le);
+    let samples = read_all(&mut source);
+    assert_eq!(samples, vec![0.0, 1.0, -1.0]);
+}
+
+/// f32le bytes should pass through unchanged.
+#[test]
+fn pcm_reader_f32le() {
+    let values = [0.25f32, -0.5, 0.75];
+    let bytes: Vec<u8> = values.iter().flat_map(|s| s.to_le_bytes()).collect();
+    let mut source = PcmReaderSource::new(Cursor::new(bytes), PcmFormat::F32Le);
+    assert_eq!(read_all(&mut source), values.to_vec());
+}
+
+/// A trailing incomplete sample should be dropped, not mis-decoded.
+#[test]
+fn pcm_reader_drops_partial_trailing_sample() {
+    let mut bytes: Vec<u8> = 1000i16.to_le_bytes().to_vec();
+    bytes.push(0x7f);
+    let mut source = PcmReaderSource::new(Cursor::new(bytes), PcmFormat::S16Le);
+    assert_eq!(read_all(&mut source).len(), 1);
+}
+
+/// Samples split across byte chunks should be decoded once the rest arrives.
+#[test]
+fn pcm_decoder_carries_split_samples() {
+    let mut decoder = PcmDecoder::new(PcmFormat::S16Le);
+    let bytes = [0x00u8, 0x40, 0x00, 0xC0];
+    assert!(decoder.push(&bytes[..1]).is_empty());
+    let samples = decoder.push(&bytes[1..3]);
+    assert_eq!(samples.len(), 1);
+    assert!((samples[0] - 0.5).abs() < 1e-3);
+    let samples = decoder.push(&bytes[3..]);
+    assert!((samples[0] + 0.5).abs() < 1e-3);
+}
+
+// ---------------------------------------------------------------------------
+// ChannelSource
+// ---------------------------------------------------------------------------
+
+/// Buffers sent before a read are returned together, in order.
+#[test]
+fn channel_source_reads_sent_buffers() {
+    let (tx, mut source) = ChannelSource::new();
+    tx.send(vec![0.1, 0.2]).unwrap();
+    tx.send(vec![0.3]).unwrap();
+    assert_eq!(source.read_samples(), vec![0.1, 0.2, 0.3]);
+    assert!(!source.is_finished());
+}
+
+/// The source finishes once the sender is dropped and everything is read.
+#[test]
+fn channel_source_finishes_when_sender_dropped() {
+    let (tx, mut source) = ChannelSource::new();
+    tx.send(vec![0.5; 100]).unwrap();
+    drop(tx);
+    assert_eq!(read_all(&mut source).len(), 100);
+    assert!(source.is_finished());
+}
+
+// ---------------------------------------------------------------------------
+// FileSource / AudioInput
+// ---------------------------------------------------------------------------
+
+/// A 16kHz WAV should be readable through FileSource.
+#[test]
+fn file_source_reads_wav() {
+    let path = std::env::temp_dir().join("dikto_source_file.wav");
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for _ in 0..8000 {
+        writer.write_sample(0.1f32).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let mut source = FileSource::open(&path).unwrap();
+    assert_eq!(read_all(&mut source).len(), 8000);
+    let _ = std::fs::remove_file(&path);
+}
+
+/// AudioInput::Samples should open into a source yielding the same samples.
+#[test]
+fn audio_input_samples_opens() {
+    let mut source = AudioInput::Samples(vec![0.1; 2000]).open().unwrap();
+    assert_eq!(read_all(source.as_mut()).len(), 2000);
+}
+
+/// A missing file should surface as AudioError::Source.
+#[test]
+fn audio_input_missing_file_is_source_error() {
+    let input = AudioInput::File("/tmp/dikto_source_missing_xyz.wav".into());
+    assert!(matches!(input.open(), Err(AudioError::Source(_))));
+}
+
+/// Custom sources use the default is_finished (never finished).
+#[test]
+fn audio_input_custom_factory() {
+    struct Silence;
+    impl AudioSource for Silence {
+        fn read_samples(&mut self) -> Vec<f32> {
+            vec![0.0; 160]
+        }
+    }
+
+    let input = AudioInput::Custom(Box::new(|| Ok(Box::new(Silence) as Box<dyn AudioSource>)));
+    let mut source = input.open().unwrap();
+    assert_eq!(source.read_samples().len(), 160);
+    assert!(!source.is_finished());
+}
+
+// ---------------------------------------------------------------------------
+// OverflowWarnings
+// ---------------------------------------------------------------------------
+
+/// Nothing is reported while the source drops nothing.
+#[test]
+fn overflow_no_drops_no_warning() {
+    let mut warnings = OverflowWarnings::new();
+    let now = Instant::now();
+    assert_eq!(warnings.check(0, now), None);
+    assert_eq!(warnings.check(0, now + 5 * OVERFLOW_WARNING_INTERVAL), None);
+}
+
+/// The first drop is reported at once with the total so far.
+#[test]
+fn overflow_first_drop_warns() {
+    let mut warnings = OverflowWarnings::new();
+    let message = warnings.check(8000, Instant::now()).unwrap();
+    assert!(message.contains("8000 samples (0.5s)"), "{message}");
+}
+
+/// Drops inside the throttle window are held back, then reported once the
+/// interval has passed, even though no more samples were dropped since.
+#[test]
+fn overflow_drops_during_throttle_are_reported_later() {
+    let mut warnings = OverflowWarnings::new();
+    let start = Instant::now();
+    assert!(warnings.check(100, start).is_some());
+    assert_eq!(
+        warnings.check(400, start + OVERFLOW_WARNING_INTERVAL / 2),
+        None
+    );
+
+    let later = start + OVERFLOW_WARNING_INTERVAL;
+    let message = warnings.check(400, later).unwrap();
+    assert!(message.contains("400 samples"), "{message}");
+    // Reported once
+    assert_eq!(
+        warnings.check(400, later + 2 * OVERFLOW_WARNING_INTERVAL),
+        None
+    );
+}
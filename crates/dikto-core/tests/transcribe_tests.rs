@@ -1,7 +1,19 @@
 // Tests for dikto_core::transcribe — TranscribeConfig defaults, TranscriptSegment
-// construction, and TranscribeError display messages.
+// construction, token/word/segment timestamp grouping, and TranscribeError display messages.
 
-use dikto_core::transcribe::{TranscribeConfig, TranscribeError, TranscriptSegment};
+use dikto_core::transcribe::{
+    group_tokens_into_words, segments_from_words, TranscribeConfig, TranscribeError,
+    TranscriptSegment, WordTimestamp,
+};
+
+/// Shorthand for a timed token or word.
+fn timed(text: &str, start: f64, end: f64) -> WordTimestamp {
+    WordTimestamp {
+        text: text.to_string(),
+        start,
+        end,
+    }
+}
 
 // ---------------------------------------------------------------------------
 // TranscribeConfig
@@ -33,6 +45,9 @@ fn transcript_segment_construction() {
     let seg = TranscriptSegment {
         text: "hello world".to_string(),
         is_final: true,
+        start: 0.0,
+        end: 1.0,
+        words: Vec::new(),
     };
     assert_eq!(seg.text, "hello world");
     assert!(seg.is_final);
@@ -44,6 +59,9 @@ fn transcript_segment_clone() {
     let seg = TranscriptSegment {
         text: "test".to_string(),
         is_final: false,
+        start: 0.0,
+        end: 1.0,
+        words: Vec::new(),
     };
     let cloned = seg.clone();
     assert_eq!(cloned.text, "test");
@@ -56,12 +74,107 @@ fn transcript_segment_debug() {
     let seg = TranscriptSegment {
         text: "debug".to_string(),
         is_final: true,
+        start: 0.0,
+        end: 1.0,
+        words: Vec::new(),
     };
     let debug_str = format!("{seg:?}");
     assert!(debug_str.contains("debug"));
     assert!(debug_str.contains("true"));
 }
 
+/// from_words should join word text and span the first to last word.
+#[test]
+fn transcript_segment_from_words() {
+    let seg =
+        TranscriptSegment::from_words(vec![timed("Hello", 0.5, 0.9), timed("world.", 1.0, 1.4)]);
+    assert_eq!(seg.text, "Hello world.");
+    assert!(seg.is_final);
+    assert!((seg.start - 0.5).abs() < 1e-9);
+    assert!((seg.end - 1.4).abs() < 1e-9);
+}
+
+/// offset should shift the segment and all of its words.
+#[test]
+fn transcript_segment_offset() {
+    let mut seg = TranscriptSegment::from_words(vec![timed("hi", 0.0, 0.5)]);
+    seg.offset(10.0);
+    assert!((seg.start - 10.0).abs() < 1e-9);
+    assert!((seg.end - 10.5).abs() < 1e-9);
+    assert!((seg.words[0].start - 10.0).abs() < 1e-9);
+}
+
+// ---------------------------------------------------------------------------
+// group_tokens_into_words
+// ---------------------------------------------------------------------------
+
+/// Whisper-style tokens: a leading space starts a new word, pieces attach.
+#[test]
+fn group_whisper_tokens() {
+    let tokens = vec![
+        timed(" Hel", 0.0, 0.2),
+        timed("lo", 0.2, 0.4),
+        timed(" world", 0.5, 0.9),
+        timed(".", 0.9, 1.0),
+    ];
+    let words = group_tokens_into_words(&tokens);
+    assert_eq!(
+        words,
+        vec![timed("Hello", 0.0, 0.4), timed("world.", 0.5, 1.0)]
+    );
+}
+
+/// SentencePiece tokens: `▁` starts a word and space-only tokens end one.
+#[test]
+fn group_sentencepiece_tokens() {
+    let tokens = vec![
+        timed("▁it", 0.0, 0.1),
+        timed("'s", 0.1, 0.2),
+        timed(" ", 0.2, 0.2),
+        timed("fine", 0.3, 0.6),
+    ];
+    let words = group_tokens_into_words(&tokens);
+    assert_eq!(
+        words,
+        vec![timed("it's", 0.0, 0.2), timed("fine", 0.3, 0.6)]
+    );
+}
+
+/// Repeated words must be kept, not deduplicated.
+#[test]
+fn group_keeps_repeated_words() {
+    let tokens = vec![timed(" that", 0.0, 0.2), timed(" that", 0.3, 0.5)];
+    assert_eq!(group_tokens_into_words(&tokens).len(), 2);
+}
+
+/// No tokens should produce no words.
+#[test]
+fn group_empty_tokens() {
+    assert!(group_tokens_into_words(&[]).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// segments_from_words
+// ---------------------------------------------------------------------------
+
+/// Words should be split into sentence segments at terminal punctuation.
+#[test]
+fn segments_split_at_sentence_end() {
+    let words = vec![
+        timed("Hi.", 0.0, 0.3),
+        timed("How", 0.5, 0.7),
+        timed("are", 0.7, 0.8),
+        timed("you?", 0.8, 1.1),
+        timed("Good", 1.5, 1.8),
+    ];
+    let segments = segments_from_words(words);
+    let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(texts, vec!["Hi.", "How are you?", "Good"]);
+    assert!((segments[1].start - 0.5).abs() < 1e-9);
+    assert!((segments[1].end - 1.1).abs() < 1e-9);
+    assert_eq!(segments[1].words.len(), 3);
+}
+
 // ---------------------------------------------------------------------------
 // TranscribeError display
 // ---------------------------------------------------------------------------
@@ -0,0 +1,43 @@
+// Tests for dikto_core::utterance — where each utterance's audio starts in
+// the session, as used to place continuous-mode segments in session time.
+
+use dikto_core::utterance::UtteranceTracker;
+
+/// A new utterance starts at the beginning of its pre-speech audio.
+#[test]
+fn speech_start_begins_utterance() {
+    let mut utterance = UtteranceTracker::new();
+    assert!(!utterance.is_active());
+    assert!(utterance.speech_start(16000));
+    assert!(utterance.is_active());
+    assert_eq!(utterance.start(), 16000);
+    assert_eq!(utterance.finish(), 16000);
+    assert!(!utterance.is_active());
+}
+
+/// Utterances of a continuous session each get their own start.
+#[test]
+fn consecutive_utterances() {
+    let mut utterance = UtteranceTracker::new();
+    assert!(utterance.speech_start(8000));
+    assert_eq!(utterance.finish(), 8000);
+    assert!(utterance.speech_start(96000));
+    assert_eq!(utterance.finish(), 96000);
+}
+
+/// Speech the VAD drops as too short returns it to idle without a SpeechEnd,
+/// so its audio stays in the session buffer. The next speech start must not
+/// move the start forward, or all segments of the utterance would be late.
+#[test]
+fn dropped_speech_keeps_first_start() {
+    let mut utterance = UtteranceTracker::new();
+    assert!(utterance.speech_start(4000));
+    // ... too short, VAD back to idle; audio keeps going to the session ...
+    assert!(!utterance.speech_start(60000));
+    assert!(utterance.is_active());
+    assert_eq!(utterance.finish(), 4000);
+
+    // After the flush, the next utterance starts fresh
+    assert!(utterance.speech_start(120000));
+    assert_eq!(utterance.start(), 120000);
+}